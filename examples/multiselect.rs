@@ -14,7 +14,7 @@ fn main() {
         "Option 10",
     ];
 
-    let selected = multiselect_input("Select an option: ", "Done", &options);
+    let selected = multiselect_input("Select an option: ", "Done", &options).unwrap();
     println!();
 
     println!("You selected: {:?}", selected);
@@ -0,0 +1,11 @@
+use painless_input::{input_password, input_password_masked};
+
+fn main() {
+    let password = input_password("Enter a password: ");
+    println!();
+    println!("{} characters", password.len());
+
+    let hidden = input_password_masked("Enter it again, hidden: ", ' ');
+    println!();
+    println!("Match: {}", password == hidden);
+}
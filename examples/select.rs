@@ -14,7 +14,7 @@ fn main() {
         "Option 10",
     ];
 
-    let selected = select_input("Select an option: ", &options);
+    let selected = select_input("Select an option: ", &options).unwrap();
     println!();
 
     println!("You selected: {}", selected);
@@ -0,0 +1,23 @@
+use painless_input::select_filter;
+
+fn main() {
+    let options = vec![
+        "Apple",
+        "Apricot",
+        "Banana",
+        "Blackberry",
+        "Blueberry",
+        "Cherry",
+        "Grape",
+        "Grapefruit",
+        "Lemon",
+        "Lime",
+        "Mango",
+        "Orange",
+    ];
+
+    let selected = select_filter("Choose a fruit: ", &options);
+    println!();
+
+    println!("You selected: {}", options[selected]);
+}
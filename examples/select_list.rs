@@ -0,0 +1,10 @@
+use painless_input::select_list;
+
+fn main() {
+    let options: Vec<String> = (1..=30).map(|i| format!("Option {}", i)).collect();
+
+    let selected = select_list("Select an option: ", &options, 5);
+    println!();
+
+    println!("You selected: {}", selected);
+}
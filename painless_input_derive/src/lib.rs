@@ -0,0 +1,148 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, ExprLit, Fields, Lit, LitStr, Type};
+
+/// Implements `painless_input::PromptForm`, prompting for each field of a struct in order.
+/// Fields are entered with `input`, except `bool` fields which are a Yes/No `select_input`.
+///
+/// Each field can be customized with a `#[prompt(...)]` attribute:
+/// - `message = "..."` is shown instead of the field name
+/// - `default = ...` is used if Enter is pressed without typing anything, a string literal is parsed as the field's type
+/// ## Example
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{PromptForm, VecEventSource};
+///
+/// #[derive(PromptForm)]
+/// struct Config {
+///     #[prompt(message = "HTTP port", default = 8080)]
+///     port: u16,
+///     #[prompt(default = "localhost")]
+///     host: String,
+///     verbose: bool,
+/// }
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('e'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('x'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (config, output) = source.run(Config::prompt);
+/// assert_eq!(config.port, 8080);
+/// assert_eq!(config.host, "ex");
+/// assert!(!config.verbose);
+/// assert!(output.contains("HTTP port: "));
+/// ```
+#[proc_macro_derive(PromptForm, attributes(prompt))]
+pub fn derive_prompt_form(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match prompt_form(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// What a `#[prompt(...)]` attribute can set on a field
+struct FieldOptions {
+    message: Option<LitStr>,
+    default: Option<Expr>,
+}
+
+fn prompt_form(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(name, "PromptForm can only be derived for structs with named fields")),
+        },
+        _ => return Err(syn::Error::new_spanned(name, "PromptForm can only be derived for structs")),
+    };
+
+    let mut prompts = Vec::new();
+    let mut field_names = Vec::new();
+
+    for field in fields {
+        let field_name = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let options = field_options(field)?;
+
+        let message = options.message.map_or_else(|| field_name.to_string(), |message| message.value());
+        let prompt_str = format!("{}: ", message);
+
+        let prompt = if is_bool(ty) {
+            if let Some(default) = &options.default {
+                return Err(syn::Error::new_spanned(default, "bool fields are a Yes/No select, which can't have a default"));
+            }
+
+            quote! {
+                matches!(::painless_input::select_input(#prompt_str, &["Yes", "No"]), Ok(0))
+            }
+        } else {
+            let default = options.default.map(|default| match &default {
+                // Strings are parsed, so `default = "localhost"` works for a String or a PathBuf
+                Expr::Lit(ExprLit { lit: Lit::Str(lit), .. }) => quote! {
+                    .default(<#ty as ::std::str::FromStr>::from_str(#lit).expect("default should parse as the field's type"))
+                },
+                _ => quote! { .default(#default) },
+            });
+
+            quote! {
+                ::painless_input::InputBuilder::<#ty>::new()
+                    .prompt(#prompt_str)
+                    #default
+                    .run()
+                    .expect("stdin closed before a valid answer was given")
+            }
+        };
+
+        prompts.push(quote! {
+            let #field_name = #prompt;
+            println!();
+        });
+        field_names.push(field_name);
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::painless_input::PromptForm for #name #ty_generics #where_clause {
+            fn prompt() -> Self {
+                #(#prompts)*
+
+                Self { #(#field_names),* }
+            }
+        }
+    })
+}
+
+fn field_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions {
+        message: None,
+        default: None,
+    };
+
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("prompt")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("message") {
+                options.message = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                options.default = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `message` or `default`"))
+            }
+        })?;
+    }
+
+    Ok(options)
+}
+
+fn is_bool(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident("bool"))
+}
@@ -0,0 +1,80 @@
+use std::io::Write;
+
+use futures_util::StreamExt;
+
+use crate::{event, Error, InputConfig, InputState, Theme, ValidationFn};
+
+/// Same as `input`, but awaits key presses instead of blocking the thread, so the prompt can run alongside other async tasks.
+/// Dropping the future, e.g. when another branch of a `select!` finishes first, stops the prompt and leaves what was typed on the line.
+///
+/// The future isn't `Send`, so it has to be awaited on the task it was created in rather than spawned.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_async;
+///
+/// # async fn run() {
+/// let num: i32 = input_async("Enter a number: ").await;
+/// println!();
+/// # }
+/// ```
+pub async fn input_async<T>(input_str: &str) -> T
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    input_async_internal(input_str, InputConfig::new(), &Theme::default())
+        .await
+        .expect("input can't be cancelled")
+}
+
+/// Same as `input_with_validation`, but awaits key presses instead of blocking the thread.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_with_validation_async;
+///
+/// # async fn run() {
+/// let num: i32 = input_with_validation_async("Enter a number: ", Box::new(|x: &i32| {
+///     if *x > 10 {
+///         Ok(())
+///     } else {
+///         Err(String::from("Number should be greater than 10"))
+///     }
+/// })).await;
+/// println!();
+/// # }
+/// ```
+pub async fn input_with_validation_async<T>(input_str: &str, validation: ValidationFn<T>) -> T
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    input_async_internal(input_str, InputConfig { validation: Some(validation), ..InputConfig::new() }, &Theme::default())
+        .await
+        .expect("input can't be cancelled")
+}
+
+/// Same as `input_internal`, but awaiting events from crossterm's `EventStream`.
+/// The timeout in `config` isn't used, as an async caller can race the prompt against a timer instead.
+pub(crate) async fn input_async_internal<T>(input_str: &str, config: InputConfig<'_, T>, theme: &Theme) -> Result<T, Error>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    let mut guard = event::stdout_terminal();
+    let out = &mut guard.out;
+
+    let mut events = crossterm::event::EventStream::new();
+
+    let mut state = InputState::new(out, input_str, config, theme);
+
+    loop {
+        out.flush().unwrap();
+
+        // The stream only ends if the terminal goes away
+        let key_event = events.next().await.expect("terminal event stream ended").unwrap();
+
+        if let Some(res) = state.handle(out, theme, key_event) {
+            return res;
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use crate::{event, input_internal, Error, InputConfig, PromptEvent, SubmitKey, Theme};
+
+/// Builds a text input out of any combination of options, for when none of the `input_*` functions fit.
+/// ## Example
+/// ```no_run
+/// use painless_input::InputBuilder;
+///
+/// let age: Result<u8, _> = InputBuilder::new()
+///     .prompt("Enter your age: ")
+///     .default(18)
+///     .max_len(3)
+///     .validate(|age| if *age > 0 { Ok(()) } else { Err(String::from("Age should be more than 0")) })
+///     .run();
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{Error, InputBuilder, VecEventSource};
+///
+/// // Enter with nothing typed returns the default
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (age, _) = source.run(|| InputBuilder::<u8>::new().prompt("Enter your age: ").default(18).run());
+/// assert_eq!(age.unwrap(), 18);
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('4'))),
+///     Event::Key(KeyEvent::from(KeyCode::Esc)),
+/// ]);
+///
+/// let (age, _) = source.run(|| InputBuilder::<u8>::new().prompt("Enter your age: ").cancelable(true).run());
+/// assert!(matches!(age, Err(Error::Cancelled)));
+/// ```
+pub struct InputBuilder<T> {
+    prompt: String,
+    config: InputConfig<'static, T>,
+    theme: Theme,
+}
+
+impl<T> InputBuilder<T> {
+    pub fn new() -> Self {
+        Self {
+            prompt: String::new(),
+            config: InputConfig::new(),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Shown before the input
+    pub fn prompt(mut self, prompt: &str) -> Self {
+        self.prompt = prompt.to_string();
+        self
+    }
+
+    /// Returned if Enter is pressed without typing anything
+    pub fn default(mut self, default: T) -> Self {
+        self.config.default = Some(default);
+        self
+    }
+
+    /// Checked once the input parses, the error message it returns is shown and the user has to try again
+    pub fn validate(mut self, validation: impl Fn(&T) -> Result<(), String> + 'static) -> Self {
+        self.config.validation = Some(Box::new(validation));
+        self
+    }
+
+    /// The most characters that can be typed, counted as graphemes
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.config.max_len = Some(max_len);
+        self
+    }
+
+    /// Shown in place of each character typed
+    pub fn mask(mut self, mask: char) -> Self {
+        self.config.mask = Some(mask);
+        self
+    }
+
+    /// Only characters `allow` returns true for can be typed
+    pub fn filter(mut self, allow: impl Fn(char) -> bool + 'static) -> Self {
+        self.config.allow = Some(Box::new(allow));
+        self
+    }
+
+    /// Whether Esc and Ctrl+C back out of the prompt with `Error::Cancelled`, instead of Ctrl+C exiting the program
+    pub fn cancelable(mut self, cancelable: bool) -> Self {
+        self.config.cancelable = cancelable;
+        self
+    }
+
+    /// How long the user has to submit the input before `Error::TimedOut` is returned
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = Some(timeout);
+        self
+    }
+
+    /// How many times the input can be rejected before `Error::TooManyAttempts` is returned, there's no limit by default
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.config.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Called with each `PromptEvent` as it happens, like keystrokes, failed validations and the submitted value.
+    /// Useful for logging or telemetry, masked input only ever passes on the mask.
+    pub fn on_event(mut self, on_event: impl Fn(PromptEvent) + 'static) -> Self {
+        self.config.on_event = Some(Box::new(on_event));
+        self
+    }
+
+    /// Text the input starts with, which the user can edit before submitting
+    pub fn initial(mut self, initial: &str) -> Self {
+        self.config.initial = Some(initial.to_string());
+        self
+    }
+
+    /// Text shown after the input, like a unit, which moves along as the input changes.
+    /// It's kept after the submitted input if `keep` is true, otherwise it's cleared.
+    pub fn suffix(mut self, suffix: &str, keep: bool) -> Self {
+        self.config.suffix = Some(suffix.to_string());
+        self.config.keep_suffix = keep;
+        self
+    }
+
+    /// The key that submits the input, Enter by default
+    pub fn submit_key(mut self, submit_key: SubmitKey) -> Self {
+        self.config.submit_key = submit_key;
+        self
+    }
+
+    /// Whether whitespace around the input is trimmed off before it's parsed, on by default.
+    /// Masked input is never trimmed, as the spaces are part of the secret.
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.config.trim = trim;
+        self
+    }
+
+    /// The theme the prompt is drawn with
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Show the prompt and wait for a valid input.
+    pub fn run(self) -> Result<T, Error>
+        where
+            T: std::str::FromStr,
+            <T as std::str::FromStr>::Err: std::fmt::Debug,
+    {
+        event::with_terminal(|source, out| input_internal(source, out, &self.prompt, self.config, &self.theme))
+    }
+
+    /// Same as `run`, but awaits key presses instead of blocking the thread.
+    /// The timeout isn't used, race the future against a timer instead.
+    #[cfg(feature = "tokio")]
+    pub async fn run_async(self) -> Result<T, Error>
+        where
+            T: std::str::FromStr,
+            <T as std::str::FromStr>::Err: std::fmt::Debug,
+    {
+        crate::async_input::input_async_internal(&self.prompt, self.config, &self.theme).await
+    }
+}
+
+impl<T> Default for InputBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
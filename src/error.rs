@@ -0,0 +1,31 @@
+use std::fmt::Display;
+
+/// Why a prompt finished without a value.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The user backed out of the prompt with Esc or Ctrl+C
+    Cancelled,
+    /// The prompt's timeout ran out before anything was submitted
+    TimedOut,
+    /// The input was rejected as many times as the prompt allows, either because it didn't parse or didn't pass validation
+    TooManyAttempts,
+    /// The prompt was given no options to choose from
+    NoOptions,
+    /// Piped stdin ran out before a valid answer was read from it
+    InputClosed,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Cancelled => write!(f, "the prompt was cancelled"),
+            Error::TimedOut => write!(f, "the prompt timed out"),
+            Error::TooManyAttempts => write!(f, "too many invalid attempts"),
+            Error::NoOptions => write!(f, "there were no options to choose from"),
+            Error::InputClosed => write!(f, "stdin closed before a valid answer was given"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
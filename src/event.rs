@@ -0,0 +1,374 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{IsTerminal, Write};
+use std::time::Duration;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+/// A source of terminal events for the prompts to read from.
+/// Prompts read from [`CrosstermEventSource`] by default, [`VecEventSource`] can be used to replay scripted events instead.
+pub trait EventSource {
+    /// Block until the next event is available and return it.
+    fn read(&mut self) -> std::io::Result<Event>;
+
+    /// Wait up to `timeout` for an event, returning whether one is available to `read`.
+    fn poll(&mut self, timeout: Duration) -> std::io::Result<bool>;
+
+    /// Whether the events come from someone at a keyboard, otherwise only typing and Enter are sent and the list widgets can't be used.
+    /// This is true unless overridden.
+    fn is_interactive(&self) -> bool {
+        true
+    }
+}
+
+/// Reads events from the real terminal through crossterm.
+pub struct CrosstermEventSource;
+
+impl EventSource for CrosstermEventSource {
+    fn read(&mut self) -> std::io::Result<Event> {
+        crossterm::event::read()
+    }
+
+    fn poll(&mut self, timeout: Duration) -> std::io::Result<bool> {
+        crossterm::event::poll(timeout)
+    }
+}
+
+/// Reads piped stdin a line at a time, sending each line as if it was typed and followed by Enter.
+/// This is used instead of `CrosstermEventSource` when stdin isn't a terminal, e.g. in CI or when a script feeds in the answers.
+struct StdinLineSource {
+    pending: VecDeque<Event>,
+    /// Whether the end of stdin has been sent as Ctrl+D, which is how a terminal ends input
+    ended: bool,
+}
+
+impl EventSource for StdinLineSource {
+    fn read(&mut self) -> std::io::Result<Event> {
+        if let Some(event) = self.pending.pop_front() {
+            return Ok(event);
+        }
+
+        let mut line = String::new();
+
+        if std::io::stdin().read_line(&mut line)? == 0 {
+            if self.ended {
+                return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "stdin was closed"));
+            }
+
+            self.ended = true;
+
+            return Ok(Event::Key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)));
+        }
+
+        let line = line.trim_end_matches(['\r', '\n']);
+
+        self.pending.extend(line.chars().map(|c| Event::Key(KeyEvent::from(KeyCode::Char(c)))));
+        self.pending.push_back(Event::Key(KeyEvent::from(KeyCode::Enter)));
+
+        self.read()
+    }
+
+    /// Reading blocks until the next line, so timeouts can't be used with piped input
+    fn poll(&mut self, _timeout: Duration) -> std::io::Result<bool> {
+        Ok(true)
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+/// Replays a scripted list of events in order, which makes it possible to drive whole interactions in tests.
+/// Reading past the end of the script returns an `UnexpectedEof` error.
+/// Polling never waits, scripted events are always available straight away and once they run out polling acts as if it timed out.
+/// ## Example
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('4'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('2'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (num, output) = source.run(|| input::<i32>("Enter a number: "));
+/// assert_eq!(num, 42);
+/// assert!(output.starts_with("Enter a number: 42"));
+/// ```
+pub struct VecEventSource {
+    events: VecDeque<Event>,
+    width: u16,
+    interactive: bool,
+}
+
+impl VecEventSource {
+    pub fn new(events: Vec<Event>) -> Self {
+        Self {
+            events: events.into(),
+            width: 80,
+            interactive: true,
+        }
+    }
+
+    /// Act like piped stdin instead of a terminal, so the list widgets refuse to run.
+    /// Piped stdin sends Ctrl+D once it runs out, script one at the end to check how a prompt handles that.
+    pub fn non_interactive(mut self) -> Self {
+        self.interactive = false;
+        self
+    }
+
+    /// How many columns wide the scripted terminal is, 80 by default.
+    /// Useful for checking how prompts behave when they don't fit on one row.
+    pub fn with_width(mut self, width: u16) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Run `f` with every prompt inside it reading from this source instead of the terminal.
+    /// Returns the result of `f` along with everything the prompts printed.
+    pub fn run<R>(self, f: impl FnOnce() -> R) -> (R, String) {
+        match self.run_catching(f) {
+            (Ok(res), output) => (res, output),
+            (Err(panic), _) => std::panic::resume_unwind(panic),
+        }
+    }
+
+    /// Same as `run`, but a panic inside `f` is caught and returned as an `Err` instead of unwinding.
+    /// The output is still returned, which makes it possible to check how the prompts clean up after a panic.
+    /// ## Example
+    /// ```
+    /// use crossterm::event::{Event, KeyCode, KeyEvent};
+    /// use painless_input::{input_with_validation, VecEventSource};
+    ///
+    /// let source = VecEventSource::new(vec![
+    ///     Event::Key(KeyEvent::from(KeyCode::Char('1'))),
+    ///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+    /// ]);
+    ///
+    /// let (res, output) = source.run_catching(|| {
+    ///     input_with_validation::<i32>("Enter a number: ", Box::new(|_| panic!("validation failed")))
+    /// });
+    ///
+    /// assert!(res.is_err());
+    /// // The cursor is shown and styling is reset even though the validation panicked
+    /// assert!(output.ends_with("\x1b[0m\x1b[?25h"));
+    /// ```
+    pub fn run_catching<R>(self, f: impl FnOnce() -> R) -> (std::thread::Result<R>, String) {
+        SCRIPT.with(|script| {
+            *script.borrow_mut() = Some(Script {
+                source: self,
+                output: Vec::new(),
+            })
+        });
+
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+
+        let script = SCRIPT.with(|script| script.borrow_mut().take()).unwrap();
+
+        (res, String::from_utf8_lossy(&script.output).into_owned())
+    }
+}
+
+impl EventSource for VecEventSource {
+    fn read(&mut self) -> std::io::Result<Event> {
+        self.events.pop_front().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "ran out of scripted events",
+            )
+        })
+    }
+
+    fn poll(&mut self, _timeout: Duration) -> std::io::Result<bool> {
+        Ok(!self.events.is_empty())
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+}
+
+/// The scripted terminal installed by `VecEventSource::run`
+struct Script {
+    source: VecEventSource,
+    output: Vec<u8>,
+}
+
+thread_local! {
+    static SCRIPT: RefCell<Option<Script>> = const { RefCell::new(None) };
+}
+
+/// Holds a script taken out of `SCRIPT` while it's in use, and puts it back when dropped, even while unwinding from a panic
+struct ScriptSlot(Option<Script>);
+
+impl Drop for ScriptSlot {
+    fn drop(&mut self) {
+        let script = self.0.take();
+
+        SCRIPT.with(|slot| *slot.borrow_mut() = script);
+    }
+}
+
+/// Where the prompts draw to, either the real stdout or the buffer of a scripted terminal.
+/// Stdout is locked and buffered for the whole prompt, so the many small writes of a frame go out together when it's flushed.
+pub(crate) enum Output<'a> {
+    Stdout(std::io::BufWriter<std::io::StdoutLock<'static>>),
+    /// The buffer of a scripted terminal, and how many columns wide it is
+    Buffer(&'a mut Vec<u8>, u16),
+}
+
+impl Output<'_> {
+    /// Width of the terminal in columns, scripted terminals are 80 columns wide unless set with `VecEventSource::with_width`
+    pub(crate) fn width(&self) -> u16 {
+        match self {
+            // Fall back to the usual width if the size can't be read, like when stdout isn't a terminal
+            Output::Stdout(_) => crossterm::terminal::size().map(|(width, _)| width).unwrap_or(80),
+            Output::Buffer(_, width) => *width,
+        }
+    }
+
+    /// Height of the terminal in rows, scripted terminals are 24 rows high
+    pub(crate) fn height(&self) -> u16 {
+        match self {
+            Output::Stdout(_) => crossterm::terminal::size().map(|(_, height)| height).unwrap_or(24),
+            Output::Buffer(..) => 24,
+        }
+    }
+
+    /// Bracketed paste makes a paste arrive as a single `Event::Paste` instead of a key press for each character.
+    /// It's only turned on for the real terminal, errors are ignored as it's not supported everywhere, e.g. older Windows consoles.
+    pub(crate) fn enable_bracketed_paste(&mut self) {
+        if let Output::Stdout(stdout) = self {
+            let _ = crossterm::execute!(stdout, crossterm::event::EnableBracketedPaste);
+        }
+    }
+
+    pub(crate) fn disable_bracketed_paste(&mut self) {
+        if let Output::Stdout(stdout) = self {
+            let _ = crossterm::execute!(stdout, crossterm::event::DisableBracketedPaste);
+        }
+    }
+
+    /// Mouse capture makes clicks arrive as `Event::Mouse`, but stops the terminal from selecting text while it's on, so it's only on while a list is shown
+    #[cfg(feature = "mouse")]
+    pub(crate) fn enable_mouse_capture(&mut self) {
+        if let Output::Stdout(stdout) = self {
+            let _ = crossterm::execute!(stdout, crossterm::event::EnableMouseCapture);
+        }
+    }
+
+    #[cfg(feature = "mouse")]
+    pub(crate) fn disable_mouse_capture(&mut self) {
+        if let Output::Stdout(stdout) = self {
+            let _ = crossterm::execute!(stdout, crossterm::event::DisableMouseCapture);
+        }
+    }
+
+    /// The row of the terminal the cursor is on, for working out which row a click landed on.
+    /// Asking the real terminal takes a round trip, so it's only done when mouse clicks can arrive. Scripted terminals start on the top row.
+    pub(crate) fn cursor_row(&mut self) -> Option<u16> {
+        match self {
+            Output::Stdout(stdout) if cfg!(feature = "mouse") => {
+                stdout.flush().ok()?;
+
+                crossterm::cursor::position().ok().map(|(_, row)| row)
+            }
+            Output::Stdout(_) => None,
+            Output::Buffer(..) => Some(0),
+        }
+    }
+}
+
+impl Write for Output<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Output::Stdout(stdout) => stdout.write(buf),
+            Output::Buffer(buffer, _) => buffer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Output::Stdout(stdout) => stdout.flush(),
+            Output::Buffer(buffer, _) => buffer.flush(),
+        }
+    }
+}
+
+/// Re-shows the cursor, resets styling, turns bracketed paste back off and leaves raw mode when dropped.
+/// This leaves the terminal usable even if a prompt panics halfway through, e.g. in a validation closure or a `Display` impl.
+pub(crate) struct TerminalGuard<'a> {
+    pub(crate) out: Output<'a>,
+    /// Whether this guard turned raw mode on, it's left alone if the caller or an outer prompt already had it on
+    disable_raw_mode: bool,
+}
+
+impl Drop for TerminalGuard<'_> {
+    fn drop(&mut self) {
+        // Errors are ignored as there's nothing more that can be done while dropping
+        if !crate::theme::no_color() {
+            let _ = crossterm::execute!(self.out, crossterm::style::ResetColor);
+        }
+
+        let _ = crossterm::execute!(self.out, crossterm::cursor::Show);
+
+        self.out.disable_bracketed_paste();
+
+        #[cfg(feature = "mouse")]
+        self.out.disable_mouse_capture();
+
+        if self.disable_raw_mode {
+            let _ = crossterm::terminal::disable_raw_mode();
+        }
+    }
+}
+
+/// The real terminal, in raw mode so key presses arrive one at a time instead of a line at a time.
+/// The async prompts use this directly, as they await events from an `EventStream` instead of reading from an `EventSource`.
+pub(crate) fn stdout_terminal() -> TerminalGuard<'static> {
+    // The rows of the list widgets are styled as strings before they're printed, which Windows consoles only understand with VT processing on.
+    // Checking for ANSI support turns it on where it can be, crossterm's own commands fall back to WinAPI calls on consoles too old for it.
+    #[cfg(windows)]
+    let _ = crossterm::ansi_support::supports_ansi();
+
+    // Raw mode can't be turned on if stdin isn't a terminal, the prompt still works with whatever input arrives then
+    let disable_raw_mode = !crossterm::terminal::is_raw_mode_enabled().unwrap_or(true)
+        && crossterm::terminal::enable_raw_mode().is_ok();
+
+    let mut guard = TerminalGuard {
+        out: Output::Stdout(std::io::BufWriter::new(std::io::stdout().lock())),
+        disable_raw_mode,
+    };
+
+    guard.out.enable_bracketed_paste();
+
+    guard
+}
+
+/// Run `f` with the scripted terminal if one is installed on this thread, or the real one otherwise.
+/// The output is wrapped in a `TerminalGuard` so the terminal is restored however `f` exits.
+pub(crate) fn with_terminal<R>(f: impl FnOnce(&mut dyn EventSource, &mut Output) -> R) -> R {
+    // Take the script out while it's in use so a validation closure can't end up borrowing it twice
+    let mut slot = ScriptSlot(SCRIPT.with(|script| script.borrow_mut().take()));
+
+    match &mut slot.0 {
+        Some(script) => {
+            let mut guard = TerminalGuard {
+                out: Output::Buffer(&mut script.output, script.source.width),
+                disable_raw_mode: false,
+            };
+
+            f(&mut script.source, &mut guard.out)
+        }
+        None => {
+            let mut guard = stdout_terminal();
+
+            if std::io::stdin().is_terminal() {
+                f(&mut CrosstermEventSource, &mut guard.out)
+            } else {
+                f(&mut StdinLineSource { pending: VecDeque::new(), ended: false }, &mut guard.out)
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+/// A struct that can be filled in by prompting for each of its fields in turn.
+/// With the `derive` feature this can be derived with `#[derive(PromptForm)]`, see the derive macro for the field attributes.
+/// ## Example
+/// ```no_run
+/// use painless_input::{input, PromptForm};
+///
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl PromptForm for Point {
+///     fn prompt() -> Self {
+///         let x = input("x: ");
+///         println!();
+///         let y = input("y: ");
+///         println!();
+///
+///         Point { x, y }
+///     }
+/// }
+///
+/// let point = Point::prompt();
+/// ```
+pub trait PromptForm: Sized {
+    /// Prompt for every field and build the struct out of the answers
+    fn prompt() -> Self;
+}
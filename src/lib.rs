@@ -1,13 +1,76 @@
 use std::fmt::Display;
-use std::io::{stdout, Write};
+use std::io::Write;
+use std::time::{Duration, Instant};
 
-//TODO: Fix moving when cursor is at the end of the line and the move is more than the length of the line
-//TODO: Arrow key movement
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+#[cfg(feature = "tokio")]
+mod async_input;
+mod builder;
+mod error;
+mod event;
+mod form;
+mod macros;
+mod matching;
+mod prompt_event;
+mod screen;
+mod secret;
+mod spinner;
+mod submit_key;
+mod theme;
+mod validator;
+mod width;
+
+#[cfg(feature = "tokio")]
+pub use async_input::{input_async, input_with_validation_async};
+pub use builder::InputBuilder;
+pub use error::Error;
+pub use event::{CrosstermEventSource, EventSource, VecEventSource};
+pub use form::PromptForm;
+pub use matching::{common_prefix, fuzzy_match, FuzzyMatch};
+pub use prompt_event::PromptEvent;
+#[cfg(feature = "derive")]
+pub use painless_input_derive::PromptForm;
+pub use screen::Screen;
+pub use secret::{prompt_secret, SecretString};
+pub use spinner::with_spinner;
+pub use submit_key::SubmitKey;
+pub use theme::Theme;
+pub use validator::{Validation, Validator};
+pub use width::display_width;
+use width::contains_rtl;
+
+use event::Output;
+
+/// A validation closure, returning an error message to be shown if the value is invalid
+type ValidationFn<T> = Box<dyn Fn(&T) -> Result<(), String>>;
+
+/// A validation closure that's also given the text the value was parsed from
+type RawValidationFn<T> = Box<dyn Fn(&str, &T) -> Result<(), String>>;
+
+/// A validation closure that can also accept a value with a warning
+type WarnValidationFn<T> = Box<dyn Fn(&T) -> Validation>;
+
+/// Builds the message shown when the text typed doesn't parse, given the text and the parse error
+type ParseErrorFn<E> = Box<dyn Fn(&str, &E) -> String>;
+
+/// Parses the text typed into a value, or gives the message to show if it can't
+type ParseFn<T> = Box<dyn Fn(&str) -> Result<T, String>>;
+
+/// Validates a new array element, given the elements entered before it
+type ElementValidationFn<'a, T> = Box<dyn Fn(&T, &[T]) -> Result<(), String> + 'a>;
+
+/// Describes the elements of an array entered so far, shown after them while typing
+type ArrayPreviewFn<'a, T> = Box<dyn Fn(&[T]) -> String + 'a>;
+
+/// Called with each thing that happens in a text prompt
+type OnEventFn<'a> = Box<dyn Fn(PromptEvent) + 'a>;
 
 /// Input a string from the user, parse it to the specified type, and validate it using a closure.
 /// The closure should return a result which is a () if the input is valid or a string error message to be shown if the input is invalid.
 /// ## Example
-/// ```
+/// ```no_run
 /// use painless_input::input_with_validation;
 ///
 /// let input: i32 = input_with_validation("Enter a number: ", Box::new(|x: &i32| {
@@ -21,654 +84,6019 @@ use std::io::{stdout, Write};
 /// ```
 pub fn input_with_validation<T>(
     input_str: &str,
-    validation: Box<dyn Fn(&T) -> Result<(), String>>,
+    validation: ValidationFn<T>,
 ) -> T
     where
         T: std::str::FromStr,
         <T as std::str::FromStr>::Err: std::fmt::Debug,
 {
-    input_internal(input_str, Some(validation))
+    event::with_terminal(|source, out| input_internal(source, out, input_str, InputConfig { validation: Some(validation), ..InputConfig::new() }, &Theme::default()))
+        .expect("stdin closed before a valid answer was given")
 }
 
-/// Input a string from the user and parse it to the specified type.
+/// Same as `input_with_validation`, but the closure is taken as is instead of boxed, so it doesn't have to be wrapped in `Box::new`.
+/// `input_with_validation` is still there for when the validation is picked at runtime and already boxed.
 /// ## Example
 /// ```
-/// use painless_input::input;
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_validated, VecEventSource};
 ///
-/// let input: i32 = input("Enter a number: ");
-/// println!();
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('3'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('4'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (num, output) = source.run(|| input_validated("Enter an even number: ", |x: &i32| {
+///     if x % 2 == 0 {
+///         Ok(())
+///     } else {
+///         Err(String::from("Should be even"))
+///     }
+/// }));
+/// assert_eq!(num, 4);
+/// assert!(output.contains("Should be even"));
 /// ```
-pub fn input<T>(input_str: &str) -> T
+pub fn input_validated<T>(
+    input_str: &str,
+    validation: impl Fn(&T) -> Result<(), String> + 'static,
+) -> T
     where
         T: std::str::FromStr,
         <T as std::str::FromStr>::Err: std::fmt::Debug,
 {
-    input_internal(input_str, None)
+    input_with_validation(input_str, Box::new(validation))
 }
 
-/// Input an array from the user, parse it to the specified type, and validate it using a closure.
-/// The array is inputted like this; first prints [ and then ask for input. On enter, if the input is empty, it will stop. Otherwise, it will parse and ask for another input.
+/// Same as `input_validated`, but the closure can change its own state, like counting how many attempts were made.
+/// It's only ever called once for each submitted value that parses.
 /// ## Example
 /// ```
-/// use painless_input::input_array;
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_validated_mut, VecEventSource};
 ///
-/// let input: Vec<i32> = input_array("Enter numbers: ");
-/// println!();
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('1'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('2'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('7'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let mut attempts = 0;
+/// let (num, output) = source.run(|| input_validated_mut("Guess the number: ", move |x: &i32| {
+///     attempts += 1;
+///
+///     if *x == 7 {
+///         Ok(())
+///     } else {
+///         Err(format!("Wrong, {} attempts so far", attempts))
+///     }
+/// }));
+/// assert_eq!(num, 7);
+/// assert!(output.contains("Wrong, 2 attempts so far"));
 /// ```
-pub fn input_array<T>(input_str: &str) -> Vec<T>
+pub fn input_validated_mut<T>(
+    input_str: &str,
+    validation: impl FnMut(&T) -> Result<(), String> + 'static,
+) -> T
     where
         T: std::str::FromStr,
         <T as std::str::FromStr>::Err: std::fmt::Debug,
 {
-    input_array_internal(input_str, None)
+    // The prompt only needs a `Fn`, a RefCell lets the closure be called mutably through a shared reference
+    let validation = std::cell::RefCell::new(validation);
+
+    input_with_validation(input_str, Box::new(move |value| (validation.borrow_mut())(value)))
 }
 
-/// Input an array from the user, parse it to the specified type, and validate it using a closure. The closure should return a result which is () if the input is valid or a string error message to be shown if the input is invalid.
-/// The array is inputted like this; first prints [ and then ask for input. On enter, if the input is empty, it will stop. Otherwise, it will parse and ask for another input.
+/// Same as `input_validated`, but the prompt gives up after the input has been rejected `max_retries` times instead of asking forever.
+/// An input that doesn't parse counts as a try the same as one the closure rejects.
+/// Returns `Error::TooManyAttempts` once the tries run out, leaving just the prompt on the line.
 /// ## Example
 /// ```
-/// use painless_input::input_array_with_validation;
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_with_retries, Error, VecEventSource};
 ///
-/// let input: Vec<i32> = input_array_with_validation("Enter numbers: ", Box::new(|x: &Vec<i32>| {
-///   if x.len() > 5 {
-///      Ok(())
-/// } else {
-///    Err(String::from("Number of elements should be greater than 5"))
-/// }
-/// }));
-/// println!();
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('x'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('3'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (res, output) = source.run(|| input_with_retries::<i32>("Enter a number over 10: ", |x| {
+///     if *x > 10 {
+///         Ok(())
+///     } else {
+///         Err(String::from("Should be over 10"))
+///     }
+/// }, 2));
+/// assert!(matches!(res, Err(Error::TooManyAttempts)));
+/// assert!(output.contains("Invalid input: 'x'"));
+/// assert!(!output.contains("Should be over 10"));
 /// ```
-pub fn input_array_with_validation<T>(
+pub fn input_with_retries<T>(
     input_str: &str,
-    validation: Box<dyn Fn(&Vec<T>) -> Result<(), String>>,
-) -> Vec<T>
+    validate: impl Fn(&T) -> Result<(), String> + 'static,
+    max_retries: usize,
+) -> Result<T, Error>
     where
         T: std::str::FromStr,
         <T as std::str::FromStr>::Err: std::fmt::Debug,
 {
-    input_array_internal(input_str, Some(validation))
+    let config = InputConfig { validation: Some(Box::new(validate)), max_retries: Some(max_retries), ..InputConfig::new() };
+
+    event::with_terminal(|source, out| input_internal(source, out, input_str, config, &Theme::default()))
 }
 
-fn input_internal<T>(
+/// Same as `input_with_validation`, but the closure is also given the text that was typed.
+/// This allows rules about how the value is written, which parsing would otherwise throw away.
+/// ## Example
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_with_raw_validation, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('0'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('7'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('7'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (num, output) = source.run(|| input_with_raw_validation::<u32>("Enter a number: ", Box::new(|raw, _| {
+///     if raw.len() > 1 && raw.starts_with('0') {
+///         Err(String::from("No leading zeros"))
+///     } else {
+///         Ok(())
+///     }
+/// })));
+///
+/// assert_eq!(num, 7);
+/// assert!(output.contains("No leading zeros"));
+/// ```
+pub fn input_with_raw_validation<T>(
     input_str: &str,
-    validation: Option<Box<dyn Fn(&T) -> Result<(), String>>>,
+    validate: RawValidationFn<T>,
 ) -> T
     where
         T: std::str::FromStr,
         <T as std::str::FromStr>::Err: std::fmt::Debug,
 {
-    crossterm::execute!(std::io::stdout(), crossterm::style::Print(input_str)).unwrap();
-    std::io::stdout().flush().unwrap();
-
-    // This is used to show error message and delete it correctly when user enters something
-    let mut current_err_msg_len = 0;
-
-    let mut input = String::new();
-    let mut res: T;
-
-    let validation_closure = if let Some(value) = validation {
-        value
-    } else {
-        Box::new(|_: &_| Ok(()))
-    };
-
-    loop {
-        let key_event = crossterm::event::read().unwrap();
-
-        match key_event {
-            crossterm::event::Event::Key(key) => {
-                if key.kind != crossterm::event::KeyEventKind::Press {
-                    continue;
-                }
-
-                match key.code {
-                    crossterm::event::KeyCode::Enter => {
-                        let parsed_input = input.parse::<T>();
-
-                        if parsed_input.is_ok() {
-                            res = parsed_input.unwrap();
-
-                            let validation_res = validation_closure(&res);
-                            if validation_res.is_ok() {
-                                break;
-                            } else {
-                                // If input is not valid, show a red bg white text error message after clearing the length of the current_input
-                                clear_left(input.len() as u16);
+    event::with_terminal(|source, out| input_internal(source, out, input_str, InputConfig { raw_validation: Some(validate), ..InputConfig::new() }, &Theme::default()))
+        .expect("stdin closed before a valid answer was given")
+}
 
-                                let error_msg = format!("{}", validation_res.unwrap_err());
+/// Same as `input_with_validation`, but the closure returns a [`Validation`], which can accept a value with a warning.
+/// The warning is shown next to the input in the theme's warning style, and the value is returned straight away.
+/// ## Example
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_with_warnings, Validation, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('0'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('9'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('9'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (age, output) = source.run(|| input_with_warnings::<u32>("Enter your age: ", Box::new(|age| match *age {
+///     0 => Validation::Err(String::from("Age should be more than 0")),
+///     1..=90 => Validation::Ok,
+///     _ => Validation::Warn(String::from("That's quite old")),
+/// })));
+///
+/// assert_eq!(age, 99);
+/// assert!(output.contains("Age should be more than 0"));
+/// assert!(output.contains("That's quite old"));
+/// ```
+pub fn input_with_warnings<T>(
+    input_str: &str,
+    validate: WarnValidationFn<T>,
+) -> T
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    event::with_terminal(|source, out| input_internal(source, out, input_str, InputConfig { warn_validation: Some(validate), ..InputConfig::new() }, &Theme::default()))
+        .expect("stdin closed before a valid answer was given")
+}
 
-                                error_display(error_msg.as_str(), &mut current_err_msg_len);
+/// Same as `input_with_validation`, but checked against a [`Validator`] made up of several rules.
+/// A plain validation closure can be passed too, as it converts into a `Validator`.
+/// ## Example
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_with_validator, Validator, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('0'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('7'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (num, output) = source.run(|| input_with_validator::<i32>("Enter a number: ", Validator::new().min(1).max(9)));
+/// assert_eq!(num, 7);
+/// assert!(output.contains("Should be at least 1"));
+/// ```
+pub fn input_with_validator<T>(input_str: &str, validator: impl Into<Validator<T>>) -> T
+    where
+        T: std::str::FromStr + 'static,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    let validator = validator.into();
 
-                                input.clear();
+    input_with_validation(input_str, Box::new(move |value| validator.validate(value)))
+}
 
-                                continue;
-                            }
-                        } else {
-                            // If input is not valid, show a red bg white text error message after clearing the length of the current_input
-                            clear_left(input.len() as u16);
+/// Input a string from the user and parse it to the specified type.
+/// ## Example
+/// ```no_run
+/// use painless_input::input;
+///
+/// let input: i32 = input("Enter a number: ");
+/// println!();
+/// ```
+/// Backspace deletes a whole grapheme at a time, so an emoji made of multiple chars is removed in one go.
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('a'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('👍'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('🏽'))),
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (text, output) = source.run(|| input::<String>("Say something: "));
+/// assert_eq!(text, "a");
+///
+/// // Both columns of the emoji were cleared, going back to column 16 and printing a space over each
+/// assert!(output.contains("👍🏽\x1b[17G  \x1b[17G"));
+/// ```
+/// Pasted text is added all at once. Newlines in it become spaces rather than submitting the input.
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Paste(String::from("hello\nworld")),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (text, _) = source.run(|| input::<String>("Say something: "));
+/// assert_eq!(text, "hello world");
+/// ```
+/// ## Long input
+/// Input wider than the terminal wraps onto the next row, and Backspace goes back up to it.
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input, VecEventSource};
+///
+/// let mut events: Vec<Event> = "abcdefghijklmnop".chars().map(|c| Event::Key(KeyEvent::from(KeyCode::Char(c)))).collect();
+/// events.extend([KeyCode::Backspace, KeyCode::Backspace, KeyCode::Backspace, KeyCode::Enter].map(|code| Event::Key(KeyEvent::from(code))));
+///
+/// // "Name: " and the first 14 letters fill the first row of a 20 column terminal
+/// let (text, output) = VecEventSource::new(events).with_width(20).run(|| input::<String>("Name: "));
+/// assert_eq!(text, "abcdefghijklm");
+/// assert!(output.contains("Name: abcdefghijklmn\r\nop"));
+///
+/// // Deleting the n goes up a row to the last column
+/// assert!(output.contains("\x1b[1A\x1b[20G"));
+/// ```
+/// ## Editing
+/// Left and Right move the cursor, and what's typed is inserted at it. Home or Ctrl+A goes to the start, and End or Ctrl+E to the end.
+/// Delete deletes the character after the cursor, Ctrl+U everything before the cursor, and Ctrl+K everything from it to the end.
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+/// use painless_input::{input, VecEventSource};
+///
+/// let typed = |text: &str| text.chars().map(|c| Event::Key(KeyEvent::from(KeyCode::Char(c)))).collect::<Vec<_>>();
+/// let keys = |code: KeyCode, times: usize| vec![Event::Key(KeyEvent::from(code)); times];
+/// let ctrl = |c: char| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+///
+/// let events = [typed("abcd"), keys(KeyCode::Left, 2), typed("x"), keys(KeyCode::Enter, 1)].concat();
+/// let (text, _) = VecEventSource::new(events).run(|| input::<String>("Text: "));
+/// assert_eq!(text, "abxcd");
+///
+/// let events = [typed("bc"), keys(KeyCode::Home, 1), typed("a"), keys(KeyCode::End, 1), typed("d"), keys(KeyCode::Enter, 1)].concat();
+/// let (text, _) = VecEventSource::new(events).run(|| input::<String>("Text: "));
+/// assert_eq!(text, "abcd");
+///
+/// let events = [typed("world"), vec![ctrl('a')], typed("hello "), vec![ctrl('e')], typed("!"), keys(KeyCode::Enter, 1)].concat();
+/// let (text, _) = VecEventSource::new(events).run(|| input::<String>("Text: "));
+/// assert_eq!(text, "hello world!");
+///
+/// // Delete at the end does nothing
+/// let events = [typed("abc"), keys(KeyCode::Left, 1), keys(KeyCode::Delete, 2), keys(KeyCode::Enter, 1)].concat();
+/// let (text, output) = VecEventSource::new(events).run(|| input::<String>("Text: "));
+/// assert_eq!(text, "ab");
+///
+/// // The c is cleared and the cursor stays after the b
+/// assert!(output.contains("\x1b[9G \x1b[9G"));
+///
+/// let events = [typed("hello world"), keys(KeyCode::Left, 5), vec![ctrl('u')], keys(KeyCode::Enter, 1)].concat();
+/// let (text, _) = VecEventSource::new(events).run(|| input::<String>("Text: "));
+/// assert_eq!(text, "world");
+///
+/// let events = [typed("hello world"), keys(KeyCode::Left, 6), vec![ctrl('k')], keys(KeyCode::Enter, 1)].concat();
+/// let (text, output) = VecEventSource::new(events).run(|| input::<String>("Text: "));
+/// assert_eq!(text, "hello");
+///
+/// // " world" is cleared and the cursor goes back to after "hello"
+/// assert!(output.contains("\x1b[12G      \x1b[12G"));
+/// ```
+/// ## Whitespace
+/// Whitespace around what was typed, like spaces, tabs and other Unicode whitespace, is trimmed off before it's parsed,
+/// so `" 42 "` is read as 42, and typing only spaces counts as typing nothing. Masked input like a password is never trimmed.
+/// Only the ends are trimmed, so leading zeros like in `"007"` are left for `T` to deal with.
+/// Use `InputBuilder::trim(false)` to get exactly what was typed, for formats where surrounding whitespace matters.
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input, InputBuilder, VecEventSource};
+///
+/// let typed = |text: &str| {
+///     let mut events: Vec<Event> = text.chars().map(|c| Event::Key(KeyEvent::from(KeyCode::Char(c)))).collect();
+///     events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+///     VecEventSource::new(events)
+/// };
+///
+/// let (num, _) = typed(" 42 ").run(|| input::<i32>("Number: "));
+/// assert_eq!(num, 42);
+///
+/// let (text, _) = typed("  indented").run(|| InputBuilder::<String>::new().trim(false).run());
+/// assert_eq!(text.unwrap(), "  indented");
+/// ```
+/// ## Right-to-left text
+/// Some terminals reorder Hebrew or Arabic text as they draw it, so the columns the text was typed into aren't where it ends up.
+/// Once the input has any right-to-left characters, each edit redraws the whole input instead of only what changed after the cursor, so nothing is left garbled.
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input, VecEventSource};
+///
+/// let mut events: Vec<Event> = "שלום".chars().map(|c| Event::Key(KeyEvent::from(KeyCode::Char(c)))).collect();
+/// events.extend([KeyCode::Backspace, KeyCode::Enter].map(|code| Event::Key(KeyEvent::from(code))));
+///
+/// let (text, output) = VecEventSource::new(events).run(|| input::<String>("Text: "));
+/// assert_eq!(text, "שלו");
+///
+/// // Deleting the last letter goes back to the start of the input and draws all of it again
+/// assert!(output.contains("\x1b[7Gשלו \x1b[10G"));
+/// ```
+/// ## Piped input
+/// If stdin isn't a terminal, each line read from it is taken as typed and followed by Enter, so answers can be piped in by a script.
+/// Invalid lines are rejected as usual and the next line is read. The list widgets like `select_input` panic instead, as they need arrow keys.
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+/// use painless_input::{input, InputBuilder, Error, VecEventSource};
+///
+/// // A line that isn't a number, then the end of stdin
+/// let piped = || VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('x'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+/// ]).non_interactive();
+///
+/// let (res, _) = piped().run_catching(|| input::<i32>("Number: "));
+/// let message = res.unwrap_err().downcast::<String>().unwrap();
+/// assert!(message.starts_with("stdin closed before a valid answer was given"));
+///
+/// // Prompts that return a `Result` give an error instead
+/// let (res, _) = piped().run(|| InputBuilder::<i32>::new().run());
+/// assert!(matches!(res, Err(Error::InputClosed)));
+/// ```
+/// ## Panics
+/// If piped stdin runs out before a valid line is read from it.
+pub fn input<T>(input_str: &str) -> T
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    input_with_theme(input_str, &Theme::default())
+}
 
-                            let error_msg = format!("Invalid input: '{}'; try again", input);
+/// Same as `input`, but the input starts with `initial` already typed, with the cursor at the end of it.
+/// Unlike a default, the text is there to be edited, e.g. when renaming something starting from its old name.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_prefilled;
+///
+/// let name: String = input_prefilled("Rename to: ", "oldname");
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_prefilled, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('n'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('e'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('w'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (name, output) = source.run(|| input_prefilled::<String>("Rename to: ", "oldname"));
+/// assert_eq!(name, "oldnew");
+/// assert!(output.starts_with("Rename to: oldname"));
+/// ```
+pub fn input_prefilled<T>(input_str: &str, initial: &str) -> T
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    let config = InputConfig { initial: Some(initial.to_string()), ..InputConfig::new() };
 
-                            error_display(error_msg.as_str(), &mut current_err_msg_len);
+    event::with_terminal(|source, out| input_internal(source, out, input_str, config, &Theme::default()))
+        .expect("stdin closed before a valid answer was given")
+}
 
-                            input.clear();
+/// Same as `input`, but `suffix` is shown after the input, like a unit in `Timeout: 30 seconds`.
+/// The suffix moves along as the input gets longer or shorter, and the cursor never goes into it. It's left after the submitted input.
+/// Use `InputBuilder::suffix` to clear it on submit instead.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_with_suffix;
+///
+/// let timeout: u32 = input_with_suffix("Timeout: ", " seconds");
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_with_suffix, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('3'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('5'))),
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('0'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (timeout, output) = source.run(|| input_with_suffix::<u32>("Timeout: ", " s"));
+/// assert_eq!(timeout, 30);
+///
+/// // The suffix is drawn after the empty input with the cursor left before it
+/// assert!(output.starts_with("Timeout:  s\x1b[10G"));
+/// // Each change clears it and draws it again after the new input
+/// assert!(output.contains("3 s\x1b[11G"));
+/// // It's left after the submitted input
+/// assert!(output.ends_with("\x1b[12G s\x1b[0m\x1b[?25h"));
+/// ```
+pub fn input_with_suffix<T>(input_str: &str, suffix: &str) -> T
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    let config = InputConfig { suffix: Some(suffix.to_string()), keep_suffix: true, ..InputConfig::new() };
 
-                            continue;
-                        }
-                    }
-                    crossterm::event::KeyCode::Char(c) => {
-                        if current_err_msg_len > 0 {
-                            clear_right(current_err_msg_len as u16);
-                            current_err_msg_len = 0;
-                        }
+    event::with_terminal(|source, out| input_internal(source, out, input_str, config, &Theme::default()))
+        .expect("stdin closed before a valid answer was given")
+}
 
-                        input.push(c);
-                        crossterm::execute!(std::io::stdout(), crossterm::style::Print(c)).unwrap();
-                        std::io::stdout().flush().unwrap();
-                    }
-                    crossterm::event::KeyCode::Backspace => {
-                        if input.is_empty() {
-                            continue;
-                        }
+/// Same as `input`, but drawn using `theme`.
+pub fn input_with_theme<T>(input_str: &str, theme: &Theme) -> T
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    event::with_terminal(|source, out| input_internal(source, out, input_str, InputConfig::new(), theme))
+        .expect("stdin closed before a valid answer was given")
+}
 
-                        input.pop();
-                        crossterm::execute!(std::io::stdout(), crossterm::cursor::MoveLeft(1))
-                            .unwrap();
-                        crossterm::execute!(std::io::stdout(), crossterm::style::Print(" "))
-                            .unwrap();
-                        crossterm::execute!(std::io::stdout(), crossterm::cursor::MoveLeft(1))
-                            .unwrap();
-                        std::io::stdout().flush().unwrap();
-                    }
-                    _ => {}
-                }
-            }
-            _ => {}
-        }
-    }
+/// Same as `input`, but the user can press Esc to back out of the prompt, in which case `None` is returned.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_optional;
+///
+/// let input: Option<i32> = input_optional("Enter a number (Esc to skip): ");
+/// println!();
+/// ```
+pub fn input_optional<T>(input_str: &str) -> Option<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    event::with_terminal(|source, out| input_internal(source, out, input_str, InputConfig { cancelable: true, ..InputConfig::new() }, &Theme::default()))
+        .ok()
+}
 
-    res
+/// Same as `input`, but at most `max_len` characters can be typed, further key presses are ignored until some are deleted.
+/// Characters are counted as graphemes, so an emoji made of multiple chars counts as one.
+/// A counter like `(3/16)` after the input shows how many have been typed, it's cleared once the input is submitted.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_with_max_len;
+///
+/// let username: String = input_with_max_len("Enter a username: ", 16);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_with_max_len, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('a'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('👍'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('🏽'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('b'))),
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('c'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (text, output) = source.run(|| input_with_max_len::<String>("Say something: ", 2));
+/// assert_eq!(text, "ac");
+///
+/// // The counter after the input is updated as it's typed
+/// assert!(output.contains(" (0/2)") && output.contains(" (2/2)") && output.contains(" (1/2)"));
+/// ```
+pub fn input_with_max_len<T>(input_str: &str, max_len: usize) -> T
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    event::with_terminal(|source, out| {
+        input_internal(source, out, input_str, InputConfig { max_len: Some(max_len), ..InputConfig::new() }, &Theme::default())
+    })
+        .expect("stdin closed before a valid answer was given")
 }
 
-fn input_array_internal<T>(
-    input_str: &str,
-    validation: Option<Box<dyn Fn(&Vec<T>) -> Result<(), String>>>,
-) -> Vec<T>
+/// Same as `input`, but only characters `allow` returns true for can be typed, others are dropped as they're typed.
+/// The input is still parsed and can fail, e.g. a number that's too big for `T`.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_filtered;
+///
+/// let num: u32 = input_filtered("Enter a number: ", |c| c.is_ascii_digit());
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_filtered, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('4'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('x'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('2'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (num, output) = source.run(|| input_filtered::<u32>("Enter a number: ", |c| c.is_ascii_digit()));
+/// assert_eq!(num, 42);
+/// assert!(!output.contains('x'));
+/// ```
+pub fn input_filtered<T>(input_str: &str, allow: impl Fn(char) -> bool) -> T
     where
         T: std::str::FromStr,
         <T as std::str::FromStr>::Err: std::fmt::Debug,
 {
-    crossterm::execute!(
-        std::io::stdout(),
-        crossterm::style::Print(input_str),
-        crossterm::style::Print("[")
-    )
-        .unwrap();
-    std::io::stdout().flush().unwrap();
+    event::with_terminal(|source, out| {
+        input_internal(source, out, input_str, InputConfig { allow: Some(Box::new(allow)), ..InputConfig::new() }, &Theme::default())
+    })
+        .expect("stdin closed before a valid answer was given")
+}
+
+/// Same as `input`, but returns `None` if the input isn't submitted within `timeout`.
+/// The timeout is for the whole prompt and isn't reset by key presses. Anything typed so far is discarded and cleared from the line.
+/// ## Example
+/// ```no_run
+/// use std::time::Duration;
+/// use painless_input::input_with_timeout;
+///
+/// let num: Option<i32> = input_with_timeout("Enter a number within 10 seconds: ", Duration::from_secs(10));
+/// println!();
+/// ```
+/// ```
+/// use std::time::Duration;
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_with_timeout, VecEventSource};
+///
+/// // The script runs out before Enter is pressed, which times out
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('4'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('2'))),
+/// ]);
+///
+/// let (num, output) = source.run(|| input_with_timeout::<i32>("Enter a number: ", Duration::from_secs(10)));
+/// assert_eq!(num, None);
+/// assert!(output.contains("42\x1b[17G  \x1b[17G"));
+/// ```
+pub fn input_with_timeout<T>(input_str: &str, timeout: Duration) -> Option<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    event::with_terminal(|source, out| {
+        input_internal(source, out, input_str, InputConfig { timeout: Some(timeout), ..InputConfig::new() }, &Theme::default())
+    })
+        .ok()
+}
+
+/// Input a string with Tab completion from `candidates`.
+/// Tab completes the input to the longest prefix shared by all candidates starting with it, and the candidates are shown as a hint if there's more than one.
+/// Pressing Tab again once nothing more can be completed cycles through the candidates. Enter submits whatever has been typed, even if it isn't a candidate.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_autocomplete;
+///
+/// let commands = vec![String::from("build"), String::from("bench"), String::from("test")];
+/// let command = input_autocomplete("Command: ", &commands);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_autocomplete, VecEventSource};
+///
+/// let commands = vec![String::from("clean"), String::from("clear"), String::from("clippy")];
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('c'))),
+///     // Completes to "cl", which is ambiguous
+///     Event::Key(KeyEvent::from(KeyCode::Tab)),
+///     // Cycles to "clean" and then "clear"
+///     Event::Key(KeyEvent::from(KeyCode::Tab)),
+///     Event::Key(KeyEvent::from(KeyCode::Tab)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (command, output) = source.run(|| input_autocomplete("Command: ", &commands));
+/// assert_eq!(command, "clear");
+/// assert!(output.contains("clean  clear  clippy"));
+/// ```
+pub fn input_autocomplete(input_str: &str, candidates: &[String]) -> String {
+    event::with_terminal(|source, out| input_autocomplete_internal(source, out, input_str, candidates, &Theme::default()))
+}
+
+/// Input a line with Up and Down walking through `history`, like in a shell.
+/// The submitted line is added to the end of `history`, unless it's empty. Editing a recalled line doesn't change the history.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_with_history;
+///
+/// let mut history = Vec::new();
+///
+/// loop {
+///     let line = input_with_history("> ", &mut history);
+///     println!();
+///
+///     if line == "exit" {
+///         break;
+///     }
+/// }
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_with_history, VecEventSource};
+///
+/// let mut history = vec![String::from("first"), String::from("second")];
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Up)),
+///     Event::Key(KeyEvent::from(KeyCode::Up)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('!'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (line, _) = source.run(|| input_with_history("> ", &mut history));
+/// assert_eq!(line, "first!");
+/// assert_eq!(history, vec!["first", "second", "first!"]);
+/// ```
+pub fn input_with_history(input_str: &str, history: &mut Vec<String>) -> String {
+    event::with_terminal(|source, out| {
+        input_internal(source, out, input_str, InputConfig { history: Some(history), ..InputConfig::new() }, &Theme::default())
+    })
+        .expect("stdin closed before a valid answer was given")
+}
+
+/// Input a path, with a leading `~` expanded to the home directory.
+/// If `must_exist` is set, paths that don't exist are rejected with an error and the user has to try again.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_path;
+///
+/// let config = input_path("Config file: ", true);
+/// println!();
+/// ```
+/// ```
+/// use std::path::PathBuf;
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_path, VecEventSource};
+///
+/// std::env::set_var("HOME", "/home/user");
+///
+/// let mut events = Vec::new();
+/// for c in "~/notes.txt".chars() {
+///     events.push(Event::Key(KeyEvent::from(KeyCode::Char(c))));
+/// }
+/// events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+///
+/// let (path, _) = VecEventSource::new(events).run(|| input_path("Save to: ", false));
+/// assert_eq!(path, PathBuf::from("/home/user/notes.txt"));
+///
+/// let mut events = Vec::new();
+/// for c in "/does/not/exist".chars() {
+///     events.push(Event::Key(KeyEvent::from(KeyCode::Char(c))));
+/// }
+/// events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+/// events.push(Event::Key(KeyEvent::from(KeyCode::Char('.'))));
+/// events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+///
+/// let (path, output) = VecEventSource::new(events).run(|| input_path("Open: ", true));
+/// assert_eq!(path, PathBuf::from("."));
+/// assert!(output.contains("'/does/not/exist' doesn't exist"));
+/// ```
+pub fn input_path(input_str: &str, must_exist: bool) -> std::path::PathBuf {
+    let validation: ValidationFn<std::path::PathBuf> = Box::new(move |path| {
+        let path = expand_tilde(path);
+
+        if must_exist && !path.exists() {
+            Err(format!("'{}' doesn't exist", path.display()))
+        } else {
+            Ok(())
+        }
+    });
+
+    let path = event::with_terminal(|source, out| {
+        input_internal(source, out, input_str, InputConfig { validation: Some(validation), ..InputConfig::new() }, &Theme::default())
+    })
+        .expect("stdin closed before a valid answer was given");
+
+    expand_tilde(&path)
+}
+
+/// Input a yes or no answer, typed out and submitted with Enter.
+/// `y`, `yes`, `true` and `1` are taken as yes and `n`, `no`, `false` and `0` as no, in any case. Anything else shows a hint and has to be typed again.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_bool;
+///
+/// let enable = input_bool("Enable feature? (yes/no) ");
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_bool, VecEventSource};
+///
+/// let mut events = Vec::new();
+/// for c in "maybe".chars() {
+///     events.push(Event::Key(KeyEvent::from(KeyCode::Char(c))));
+/// }
+/// events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+/// for c in "YES".chars() {
+///     events.push(Event::Key(KeyEvent::from(KeyCode::Char(c))));
+/// }
+/// events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+///
+/// let (answer, output) = VecEventSource::new(events).run(|| input_bool("Enable feature? "));
+/// assert!(answer);
+/// assert!(output.contains("Answer yes or no"));
+/// ```
+pub fn input_bool(input_str: &str) -> bool {
+    let validation: ValidationFn<String> = Box::new(|answer| {
+        parse_bool(answer).map(|_| ()).ok_or_else(|| String::from("Answer yes or no"))
+    });
+
+    let answer = event::with_terminal(|source, out| {
+        input_internal(source, out, input_str, InputConfig { validation: Some(validation), ..InputConfig::new() }, &Theme::default())
+    })
+        .expect("stdin closed before a valid answer was given");
+
+    parse_bool(&answer).unwrap()
+}
+
+/// Input a length of time written as numbers with units, like `30s`, `5m`, `1.5h` or `1h 30m`.
+/// The units are `ms`, `s`, `m`, `h` and `d`. Anything else shows what's wrong with it and has to be typed again.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_duration;
+///
+/// let interval = input_duration("Check every: ");
+/// println!();
+/// ```
+/// ```
+/// use std::time::Duration;
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_duration, VecEventSource};
+///
+/// let typed = |text: &str| {
+///     let mut events = Vec::new();
+///     for c in text.chars() {
+///         let code = if c == '\n' { KeyCode::Enter } else { KeyCode::Char(c) };
+///         events.push(Event::Key(KeyEvent::from(code)));
+///     }
+///     VecEventSource::new(events)
+/// };
+///
+/// let (duration, _) = typed("1h30m\n").run(|| input_duration("Timeout: "));
+/// assert_eq!(duration, Duration::from_secs(5400));
+///
+/// let (duration, _) = typed("1.5s 250ms\n").run(|| input_duration("Timeout: "));
+/// assert_eq!(duration, Duration::from_millis(1750));
+///
+/// let (duration, output) = typed("90\n5y\nsoon\n2d\n").run(|| input_duration("Timeout: "));
+/// assert_eq!(duration, Duration::from_secs(2 * 86400));
+/// assert!(output.contains("'90' needs a unit, like 90s or 90m"));
+/// assert!(output.contains("'y' isn't a unit, use ms, s, m, h or d"));
+/// assert!(output.contains("Enter a duration like 30s, 5m or 1h30m"));
+/// ```
+pub fn input_duration(input_str: &str) -> Duration {
+    let validation: ValidationFn<String> = Box::new(|text| parse_duration(text).map(|_| ()));
+
+    let text = event::with_terminal(|source, out| {
+        input_internal(source, out, input_str, InputConfig { validation: Some(validation), ..InputConfig::new() }, &Theme::default())
+    })
+        .expect("stdin closed before a valid answer was given");
+
+    parse_duration(&text).unwrap()
+}
+
+/// Input a single character, returned as soon as its key is pressed without waiting for Enter.
+/// The character is echoed after the prompt. Useful for hotkey menus, where each choice is a key.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_char;
+///
+/// let key = input_char("Press any key to continue... ");
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_char, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     // Keys that aren't characters are ignored
+///     Event::Key(KeyEvent::from(KeyCode::Up)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('q'))),
+/// ]);
+///
+/// let (key, output) = source.run(|| input_char("Press a key: "));
+/// assert_eq!(key, 'q');
+/// assert!(output.starts_with("Press a key: q"));
+/// ```
+pub fn input_char(input_str: &str) -> char {
+    event::with_terminal(|source, out| input_char_internal(source, out, input_str, |_| true, &Theme::default()))
+}
+
+/// Same as `input_char`, but only a character `allow` returns true for is accepted.
+/// Pressing any other key shows an error and waits for another one.
+/// ## Example
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_char_filtered, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('x'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('s'))),
+/// ]);
+///
+/// let (key, output) = source.run(|| input_char_filtered("[s]ave, [q]uit: ", |c| "sq".contains(c)));
+/// assert_eq!(key, 's');
+/// assert!(output.contains("'x' isn't one of the choices"));
+/// ```
+pub fn input_char_filtered(input_str: &str, allow: impl Fn(char) -> bool) -> char {
+    event::with_terminal(|source, out| input_char_internal(source, out, input_str, allow, &Theme::default()))
+}
+
+/// Input an IPv4 or IPv6 address, showing an example of what one looks like if the input isn't one.
+/// ## Example
+/// ```
+/// use std::net::{IpAddr, Ipv4Addr};
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_ip, VecEventSource};
+///
+/// let mut events = Vec::new();
+/// for c in "192.168.1\n192.168.1.1\n".chars() {
+///     let code = if c == '\n' { KeyCode::Enter } else { KeyCode::Char(c) };
+///     events.push(Event::Key(KeyEvent::from(code)));
+/// }
+///
+/// let (ip, output) = VecEventSource::new(events).run(|| input_ip("Server IP: "));
+/// assert_eq!(ip, IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)));
+/// assert!(output.contains("Not a valid IP address (e.g. 192.168.1.1)"));
+/// ```
+pub fn input_ip(input_str: &str) -> std::net::IpAddr {
+    input_with_error_fmt(input_str, Box::new(|_, _| String::from("Not a valid IP address (e.g. 192.168.1.1)")))
+}
+
+/// Input an IP address and port, showing an example of what one looks like if the input isn't one.
+/// IPv6 addresses have to be in square brackets, like `[::1]:8080`.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_socket_addr;
+///
+/// let addr = input_socket_addr("Listen on: ");
+/// println!();
+/// ```
+pub fn input_socket_addr(input_str: &str) -> std::net::SocketAddr {
+    input_with_error_fmt(input_str, Box::new(|_, _| String::from("Not a valid socket address (e.g. 127.0.0.1:8080)")))
+}
+
+/// Same as `input`, but if the text doesn't parse, `fmt` is given the text and the parse error to build the message shown instead of the usual one.
+/// This allows telling apart different ways the input can be wrong, like a number that's too big and one that isn't a number at all.
+/// ## Example
+/// ```
+/// use std::num::IntErrorKind;
+///
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_with_error_fmt, VecEventSource};
+///
+/// let mut events = Vec::new();
+/// for c in "300\nabc\n30\n".chars() {
+///     let code = if c == '\n' { KeyCode::Enter } else { KeyCode::Char(c) };
+///     events.push(Event::Key(KeyEvent::from(code)));
+/// }
+///
+/// let (num, output) = VecEventSource::new(events).run(|| input_with_error_fmt::<u8>("Enter a number: ", Box::new(|raw, error| {
+///     match error.kind() {
+///         IntErrorKind::PosOverflow => format!("{} is too big, the most is 255", raw),
+///         _ => format!("'{}' isn't a number", raw),
+///     }
+/// })));
+///
+/// assert_eq!(num, 30);
+/// assert!(output.contains("300 is too big, the most is 255"));
+/// assert!(output.contains("'abc' isn't a number"));
+/// ```
+pub fn input_with_error_fmt<T>(input_str: &str, fmt: ParseErrorFn<T::Err>) -> T
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug + 'static,
+{
+    // Parsed as text first, so the parse error can be turned into the message while the prompt is still up
+    let validation: ValidationFn<String> = Box::new(move |text| {
+        text.parse::<T>().map(|_| ()).map_err(|error| fmt(text, &error))
+    });
+
+    let text = event::with_terminal(|source, out| {
+        input_internal(source, out, input_str, InputConfig { validation: Some(validation), ..InputConfig::new() }, &Theme::default())
+    })
+        .expect("stdin closed before a valid answer was given");
+
+    text.parse().unwrap()
+}
+
+/// Same as `input`, but the text is parsed by `parse` instead of with `FromStr`, so `T` can be any type.
+/// `parse` returns the message to show if the text can't be parsed. Useful for formats `FromStr` doesn't handle, like numbers written with thousands separators.
+/// The text is trimmed before it's parsed, like with `input`, and `parse` is called once more on the accepted text.
+/// ## Example
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_parse, VecEventSource};
+///
+/// let mut events = Vec::new();
+/// for c in "1,0x0\n1,000\n".chars() {
+///     let code = if c == '\n' { KeyCode::Enter } else { KeyCode::Char(c) };
+///     events.push(Event::Key(KeyEvent::from(code)));
+/// }
+///
+/// let (num, output) = VecEventSource::new(events).run(|| input_parse("Amount: ", Box::new(|text| {
+///     text.replace(',', "").parse::<u64>().map_err(|_| format!("'{}' isn't a number like 1,000", text))
+/// })));
+/// assert_eq!(num, 1000);
+/// assert!(output.contains("'1,0x0' isn't a number like 1,000"));
+/// ```
+pub fn input_parse<T>(input_str: &str, parse: ParseFn<T>) -> T
+    where T: 'static
+{
+    // Parsed as text first, so the message can be shown while the prompt is still up
+    let parse = std::rc::Rc::new(parse);
+    let validate = std::rc::Rc::clone(&parse);
+
+    let validation: ValidationFn<String> = Box::new(move |text| validate(text).map(|_| ()));
+
+    let text = event::with_terminal(|source, out| {
+        input_internal(source, out, input_str, InputConfig { validation: Some(validation), ..InputConfig::new() }, &Theme::default())
+    })
+        .expect("stdin closed before a valid answer was given");
+
+    parse(&text).unwrap()
+}
+
+/// Input text over multiple lines, where Enter starts a new line and Ctrl+D or Alt+Enter submits.
+/// The lines after the first are indented to line up with the first, and the returned string keeps the newlines.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_multiline;
+///
+/// let message = input_multiline("Commit message: ");
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+/// use painless_input::{input_multiline, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('a'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('b'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     // Deletes the empty line, going back to the end of the one above
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('c'))),
+///     Event::Key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+/// ]);
+///
+/// let (text, _) = source.run(|| input_multiline("Message: "));
+/// assert_eq!(text, "a\nbc");
+/// ```
+pub fn input_multiline(input_str: &str) -> String {
+    event::with_terminal(|source, out| input_multiline_internal(source, out, input_str, &[SubmitKey::CtrlD, SubmitKey::AltEnter]))
+}
+
+/// Same as `input_multiline`, but only `submit` submits the input.
+/// Enter starts a new line unless it's the submit key, in which case lines can only be added by pasting them.
+/// ## Example
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+/// use painless_input::{input_multiline_with_submit, SubmitKey, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('a'))),
+///     // Ctrl+D isn't the submit key, so it's ignored
+///     Event::Key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('b'))),
+///     Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT)),
+/// ]);
+///
+/// let (text, _) = source.run(|| input_multiline_with_submit("Message: ", SubmitKey::AltEnter));
+/// assert_eq!(text, "a\nb");
+/// ```
+pub fn input_multiline_with_submit(input_str: &str, submit: SubmitKey) -> String {
+    event::with_terminal(|source, out| input_multiline_internal(source, out, input_str, &[submit]))
+}
+
+/// Input a password from the user, showing a * for each character typed instead of the character itself.
+/// Ctrl+R toggles between the stars and what was actually typed.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_password;
+///
+/// let password = input_password("Enter your password: ");
+/// println!();
+/// ```
+pub fn input_password(input_str: &str) -> String {
+    input_password_masked(input_str, '*')
+}
+
+/// Same as `input_password`, but shows `mask` for each character typed.
+/// A mask of ' ' hides the input completely while still moving the cursor along as the user types.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_password_masked;
+///
+/// let password = input_password_masked("Enter your password: ", '•');
+/// println!();
+/// ```
+/// Ctrl+R toggles between the mask and what was actually typed, to check for typos.
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+/// use painless_input::{input_password_masked, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('a'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('b'))),
+///     Event::Key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('c'))),
+///     Event::Key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (password, output) = source.run(|| input_password_masked("Password: ", '•'));
+/// assert_eq!(password, "abc");
+///
+/// // Revealed, then masked again
+/// assert!(output.contains("••\x1b[11G  \x1b[11Gab"));
+/// assert!(output.contains("abc\x1b[11G   \x1b[11G•••"));
+/// ```
+/// Spaces around the password are kept, as they're part of it.
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_password_masked, VecEventSource};
+///
+/// let mut events: Vec<Event> = " pa ss ".chars().map(|c| Event::Key(KeyEvent::from(KeyCode::Char(c)))).collect();
+/// events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+///
+/// let (password, _) = VecEventSource::new(events).run(|| input_password_masked("Password: ", '•'));
+/// assert_eq!(password, " pa ss ");
+/// ```
+pub fn input_password_masked(input_str: &str, mask: char) -> String {
+    event::with_terminal(|source, out| input_internal(source, out, input_str, InputConfig { mask: Some(mask), ..InputConfig::new() }, &Theme::default()))
+        .expect("stdin closed before a valid answer was given")
+}
+
+/// Input a new password twice, on two lines, returning it once both match.
+/// If they don't match, both prompts are cleared and asked again with an error shown above them.
+/// ## Example
+/// ```no_run
+/// use painless_input::password_confirm;
+///
+/// let password = password_confirm("New password: ", "Confirm password: ");
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{password_confirm, VecEventSource};
+///
+/// let mut events = Vec::new();
+/// for attempt in ["ab", "ax", "ab", "ab"] {
+///     for c in attempt.chars() {
+///         events.push(Event::Key(KeyEvent::from(KeyCode::Char(c))));
+///     }
+///     events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+/// }
+///
+/// let (password, output) = VecEventSource::new(events).run(|| password_confirm("New: ", "Again: "));
+/// assert_eq!(password, "ab");
+/// assert!(output.contains("Passwords don't match"));
+/// assert!(!output.contains("ab"));
+///
+/// // Spaces around the password are kept
+/// let mut events = Vec::new();
+/// for _ in 0..2 {
+///     events.extend(" pa ss ".chars().map(|c| Event::Key(KeyEvent::from(KeyCode::Char(c)))));
+///     events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+/// }
+///
+/// let (password, _) = VecEventSource::new(events).run(|| password_confirm("New: ", "Again: "));
+/// assert_eq!(password, " pa ss ");
+/// ```
+pub fn password_confirm(prompt1: &str, prompt2: &str) -> String {
+    event::with_terminal(|source, out| password_confirm_internal(source, out, prompt1, prompt2, &Theme::default()))
+}
+
+/// Input an array from the user, parse it to the specified type, and validate it using a closure.
+/// The array is inputted like this; first prints [ and then ask for input. On enter, if the input is empty, it will stop. Otherwise, it will parse and ask for another input.
+/// Esc stops straight away with the elements entered so far, dropping whatever is typed of the next one.
+///
+/// Left and Right go back to the elements already entered to edit them, Enter saves the change and goes back to the end of the array.
+/// Moving to another element drops the change, and emptying an element and pressing Enter removes it.
+///
+/// Pasting an array like `[1, 2, 3]`, or values separated by tabs or newlines like a row or column copied from a spreadsheet, adds each of them as an element.
+/// Any that don't parse or pass validation are left out and listed in an error, the rest are still added.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_array;
+///
+/// let input: Vec<i32> = input_array("Enter numbers: ");
+/// println!();
+/// ```
+/// Wide characters take up two columns, so deleting an element of CJK text clears twice as many columns as it has characters.
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_array, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('日'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('本'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (words, output) = source.run(|| input_array::<String>("Enter words: "));
+/// assert!(words.is_empty());
+///
+/// // 2 columns for ", " and 4 for the two characters
+/// let after_element = output.split("日本").nth(1).unwrap();
+/// assert_eq!(after_element.matches("\x1b[1D \x1b[1D").count(), 6);
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_array, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('1'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('2'))),
+///     Event::Key(KeyEvent::from(KeyCode::Esc)),
+/// ]);
+///
+/// let (numbers, output) = source.run(|| input_array::<i32>("Enter numbers: "));
+/// assert_eq!(numbers, vec![1]);
+/// assert!(output.ends_with("]\x1b[0m\x1b[?25h"));
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_array, VecEventSource};
+///
+/// let typed = |text: &str| text.chars().map(|c| Event::Key(KeyEvent::from(KeyCode::Char(c)))).collect::<Vec<_>>();
+/// let key = |code: KeyCode| vec![Event::Key(KeyEvent::from(code))];
+///
+/// let events = [
+///     typed("1"), key(KeyCode::Enter), typed("2"), key(KeyCode::Enter), typed("3"),
+///     // Back to the 1, which is changed to 9, then on to finish the 3 that was being typed
+///     key(KeyCode::Left), key(KeyCode::Left), key(KeyCode::Backspace), typed("9"), key(KeyCode::Enter),
+///     key(KeyCode::Enter), key(KeyCode::Enter),
+/// ].concat();
+///
+/// let (numbers, output) = VecEventSource::new(events).run(|| input_array::<i32>("Enter numbers: "));
+/// assert_eq!(numbers, vec![9, 2, 3]);
+///
+/// // The line is drawn again with the element being edited in bold, and the cursor at the end of it
+/// assert!(output.contains("\rEnter numbers: [\x1b[1m1\x1b[0m, 2, 3\x1b[K\x1b[18G"));
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_array, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Paste(String::from("[1, 2, x]")),
+///     Event::Paste(String::from("4\t5\n6")),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (numbers, output) = source.run(|| input_array::<i32>("Enter numbers: "));
+/// assert_eq!(numbers, vec![1, 2, 4, 5, 6]);
+/// assert!(output.contains("Left out 'x'"));
+/// assert!(output.contains("1, 2, "));
+/// ```
+/// ## Panics
+/// If piped stdin runs out before the array is finished with an empty line.
+pub fn input_array<T>(input_str: &str) -> Vec<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    input_array_with_theme(input_str, &Theme::default())
+}
+
+/// Same as `input_array`, but drawn using `theme`.
+pub fn input_array_with_theme<T>(input_str: &str, theme: &Theme) -> Vec<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    event::with_terminal(|source, out| input_array_internal(source, out, input_str, ArrayConfig::new(), theme))
+}
+
+/// Same as `input_array`, but the elements are shown between `open` and `close` and split up by `separator` instead of `[`, `]` and `, `.
+/// These are the `array_open`, `array_close` and `array_separator` of a [`Theme`], set them there to use them with the other array inputs.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_array_with_separator_display;
+///
+/// let input: Vec<i32> = input_array_with_separator_display("Enter numbers: ", "{", "; ", "}");
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_array_with_separator_display, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('1'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('2'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (numbers, output) = source.run(|| input_array_with_separator_display::<i32>("Enter numbers: ", "{", "; ", "}"));
+/// assert_eq!(numbers, vec![1, 2]);
+/// assert!(output.starts_with("Enter numbers: {1; 2; "));
+/// // The last separator is cleared before the closing bracket
+/// assert!(output.ends_with("2; \x1b[1D \x1b[1D\x1b[1D \x1b[1D}\x1b[0m\x1b[?25h"));
+/// ```
+/// Deleting an element with Backspace clears its separator along with it, however wide the separator is.
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_array_with_separator_display, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('1'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('2'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (numbers, output) = source.run(|| input_array_with_separator_display::<i32>("Enter numbers: ", "<", " | ", ">"));
+/// assert_eq!(numbers, vec![1]);
+///
+/// // 3 columns for " | " and 1 for the 2, then 3 more for the separator after the 1 when finishing
+/// let after_two = output.split("2 | ").nth(1).unwrap();
+/// assert_eq!(after_two.matches("\x1b[1D \x1b[1D").count(), 7);
+/// assert!(after_two.ends_with(">\x1b[0m\x1b[?25h"));
+/// ```
+pub fn input_array_with_separator_display<T>(input_str: &str, open: &str, separator: &str, close: &str) -> Vec<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    let theme = Theme {
+        array_open: open.to_string(),
+        array_separator: separator.to_string(),
+        array_close: close.to_string(),
+        ..Theme::default()
+    };
+
+    input_array_with_theme(input_str, &theme)
+}
+
+/// Input an array from the user, parse it to the specified type, and validate it using a closure. The closure should return a result which is () if the input is valid or a string error message to be shown if the input is invalid.
+/// The array is inputted like this; first prints [ and then ask for input. On enter, if the input is empty, it will stop. Otherwise, it will parse and ask for another input.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_array_with_validation;
+///
+/// let input: Vec<i32> = input_array_with_validation("Enter numbers: ", Box::new(|x: &Vec<i32>| {
+///   if x.len() > 5 {
+///      Ok(())
+/// } else {
+///    Err(String::from("Number of elements should be greater than 5"))
+/// }
+/// }));
+/// println!();
+/// ```
+pub fn input_array_with_validation<T>(
+    input_str: &str,
+    validation: ValidationFn<Vec<T>>,
+) -> Vec<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    event::with_terminal(|source, out| {
+        input_array_internal(source, out, input_str, ArrayConfig { validation: Some(validation), ..ArrayConfig::new() }, &Theme::default())
+    })
+}
+
+/// Input an array from the user, validating each element using a closure as soon as it's entered.
+/// If an element is invalid, the error message is shown and just that element has to be typed again.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_array_element_validated;
+///
+/// let input: Vec<i32> = input_array_element_validated("Enter positive numbers: ", Box::new(|x: &i32| {
+///     if *x > 0 {
+///         Ok(())
+///     } else {
+///         Err(String::from("Number should be positive"))
+///     }
+/// }));
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_array_element_validated, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('1'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('0'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('2'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (nums, output) = source.run(|| {
+///     input_array_element_validated::<i32>("Enter numbers: ", Box::new(|x| {
+///         if *x > 0 { Ok(()) } else { Err(String::from("Number should be positive")) }
+///     }))
+/// });
+/// assert_eq!(nums, vec![1, 2]);
+/// assert!(output.contains("Number should be positive"));
+/// ```
+pub fn input_array_element_validated<T>(
+    input_str: &str,
+    validate: ValidationFn<T>,
+) -> Vec<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    let config = ArrayConfig {
+        element_validation: Some(Box::new(move |value: &T, _: &[T]| validate(value))),
+        ..ArrayConfig::new()
+    };
+
+    event::with_terminal(|source, out| input_array_internal(source, out, input_str, config, &Theme::default()))
+}
+
+/// Input an array without duplicates. An element that was already entered is rejected with an error and has to be typed again.
+/// The elements are returned in the order they were entered.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_array_unique;
+///
+/// let tags: Vec<String> = input_array_unique("Enter tags: ");
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_array_unique, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('b'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('a'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('b'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (tags, output) = source.run(|| input_array_unique::<String>("Enter tags: "));
+/// assert_eq!(tags, vec!["b", "a"]);
+/// assert!(output.contains("Duplicate input; try again"));
+/// ```
+pub fn input_array_unique<T>(input_str: &str) -> Vec<T>
+    where
+        T: std::str::FromStr + PartialEq,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    let config = ArrayConfig {
+        element_validation: Some(Box::new(|value: &T, previous: &[T]| {
+            if previous.contains(value) {
+                Err(String::from("Duplicate input; try again"))
+            } else {
+                Ok(())
+            }
+        })),
+        ..ArrayConfig::new()
+    };
+
+    event::with_terminal(|source, out| input_array_internal(source, out, input_str, config, &Theme::default()))
+}
+
+/// Same as `input_array`, but Enter with nothing entered yet shows an error instead of returning an empty array.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_array_nonempty;
+///
+/// let files: Vec<String> = input_array_nonempty("Enter files to open: ");
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_array_nonempty, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('1'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (nums, output) = source.run(|| input_array_nonempty::<i32>("Enter numbers: "));
+/// assert_eq!(nums, vec![1]);
+/// assert!(output.contains("Enter at least one value"));
+/// ```
+pub fn input_array_nonempty<T>(input_str: &str) -> Vec<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    let config = ArrayConfig {
+        nonempty: true,
+        ..ArrayConfig::new()
+    };
+
+    event::with_terminal(|source, out| input_array_internal(source, out, input_str, config, &Theme::default()))
+}
+
+/// Input `key=value` pairs, like environment variables or headers, entered one at a time like the elements of `input_array`.
+/// Each entry is split on its first `=`, so the value can have more of them, and whitespace around the key and the value is trimmed.
+/// An entry without an `=` or without a key shows an error and has to be typed again. A key entered twice keeps the last value.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_map;
+///
+/// let env = input_map("Environment: ");
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_map, VecEventSource};
+///
+/// let mut events = Vec::new();
+/// for c in "PATH=/bin\nDEBUG\nQUERY = a=b\nPATH=/usr/bin\n\n".chars() {
+///     let code = if c == '\n' { KeyCode::Enter } else { KeyCode::Char(c) };
+///     events.push(Event::Key(KeyEvent::from(code)));
+/// }
+///
+/// let (env, output) = VecEventSource::new(events).run(|| input_map("Environment: "));
+/// assert_eq!(env.len(), 2);
+/// assert_eq!(env["PATH"], "/usr/bin");
+/// assert_eq!(env["QUERY"], "a=b");
+/// assert!(output.contains("'DEBUG' has no =, enter it as key=value"));
+/// ```
+pub fn input_map(input_str: &str) -> std::collections::HashMap<String, String> {
+    input_map_internal(input_str, false)
+}
+
+/// Same as `input_map`, but entering a key that's already set shows an error instead of overwriting its value.
+/// ## Example
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_map_unique, VecEventSource};
+///
+/// let mut events = Vec::new();
+/// for c in "Accept=text/html\naccept=*/*\nAccept=*/*\n\n".chars() {
+///     let code = if c == '\n' { KeyCode::Enter } else { KeyCode::Char(c) };
+///     events.push(Event::Key(KeyEvent::from(code)));
+/// }
+///
+/// let (headers, output) = VecEventSource::new(events).run(|| input_map_unique("Headers: "));
+/// assert_eq!(headers.len(), 2);
+/// assert_eq!(headers["Accept"], "text/html");
+/// assert!(output.contains("'Accept' is already set"));
+/// ```
+pub fn input_map_unique(input_str: &str) -> std::collections::HashMap<String, String> {
+    input_map_internal(input_str, true)
+}
+
+/// Entries are taken in as text and checked as they're entered, then split into the map once they're all in
+fn input_map_internal(input_str: &str, unique: bool) -> std::collections::HashMap<String, String> {
+    let config = ArrayConfig {
+        element_validation: Some(Box::new(move |entry: &String, previous: &[String]| {
+            let Some((key, _)) = split_entry(entry) else {
+                return Err(format!("'{}' has no =, enter it as key=value", entry));
+            };
+
+            if key.is_empty() {
+                return Err(String::from("The key can't be empty"));
+            }
+
+            if unique && previous.iter().any(|previous| split_entry(previous).is_some_and(|(previous, _)| previous == key)) {
+                return Err(format!("'{}' is already set", key));
+            }
+
+            Ok(())
+        })),
+        ..ArrayConfig::new()
+    };
+
+    let entries: Vec<String> = event::with_terminal(|source, out| input_array_internal(source, out, input_str, config, &Theme::default()));
+
+    entries
+        .iter()
+        .filter_map(|entry| split_entry(entry))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// The key and value of a `key=value` entry, split on the first `=` and trimmed
+fn split_entry(entry: &str) -> Option<(&str, &str)> {
+    entry.split_once('=').map(|(key, value)| (key.trim(), value.trim()))
+}
+
+/// Input an array on a single line, with the elements separated by `delimiter`.
+/// Whitespace around each element is trimmed, and empty elements at the end are ignored so a trailing delimiter is fine.
+/// If an element can't be parsed, the error is shown after the line and the user can go back and fix it.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_array_delimited;
+///
+/// let input: Vec<i32> = input_array_delimited("Enter numbers separated by commas: ", ',');
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_array_delimited, VecEventSource};
+///
+/// let mut events = Vec::new();
+/// for c in "1, x,".chars() {
+///     events.push(Event::Key(KeyEvent::from(KeyCode::Char(c))));
+/// }
+/// events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+/// // Fix the second element
+/// events.push(Event::Key(KeyEvent::from(KeyCode::Backspace)));
+/// events.push(Event::Key(KeyEvent::from(KeyCode::Backspace)));
+/// events.push(Event::Key(KeyEvent::from(KeyCode::Char('2'))));
+/// events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+///
+/// let (nums, output) = VecEventSource::new(events).run(|| input_array_delimited::<i32>("Enter numbers: ", ','));
+/// assert_eq!(nums, vec![1, 2]);
+/// assert!(output.contains("Invalid element 2: 'x'"));
+/// ```
+/// A paste is added to the line as if it was typed, with newlines turned into spaces:
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_array_delimited, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Paste(String::from("1, 2,\n3")),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (nums, output) = source.run(|| input_array_delimited::<i32>("Enter numbers: ", ','));
+/// assert_eq!(nums, vec![1, 2, 3]);
+/// assert!(output.contains("Enter numbers: 1, 2, 3"));
+/// ```
+pub fn input_array_delimited<T>(input_str: &str, delimiter: char) -> Vec<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    event::with_terminal(|source, out| input_array_delimited_internal(source, out, input_str, delimiter, &Theme::default()))
+}
+
+/// Same as `input_array`, but how many numbers have been entered and what they add up to is shown after them while typing, like `(n=3, sum=12)`.
+/// The preview is cleared once the array is finished.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_array_numeric_preview;
+///
+/// let prices: Vec<f64> = input_array_numeric_preview("Prices: ");
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_array_numeric_preview, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('5'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('7'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (numbers, output) = source.run(|| input_array_numeric_preview::<i32>("Numbers: "));
+/// assert_eq!(numbers, vec![5, 7]);
+/// assert!(output.contains("(n=0, sum=0)"));
+/// assert!(output.contains("(n=2, sum=12)"));
+/// // Cleared before the closing bracket
+/// assert!(output.ends_with("\x1b[K\x1b[1D \x1b[1D\x1b[1D \x1b[1D]\x1b[0m\x1b[?25h"));
+/// ```
+pub fn input_array_numeric_preview<T>(input_str: &str) -> Vec<T>
+    where
+        T: std::str::FromStr + Into<f64> + Copy,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    let config = ArrayConfig {
+        preview: Some(Box::new(|numbers: &[T]| {
+            // Folded from 0 as an empty f64 sum() is -0
+            let sum = numbers.iter().fold(0.0, |sum, number| sum + (*number).into());
+
+            format!("(n={}, sum={})", numbers.len(), sum)
+        })),
+        ..ArrayConfig::new()
+    };
+
+    event::with_terminal(|source, out| input_array_internal(source, out, input_str, config, &Theme::default()))
+}
+
+/// Input exactly `n` elements, returning once the last one is entered without needing an empty enter.
+/// The elements still to be entered are shown as `_`, like `[1, 2, _]`.
+/// ## Example
+/// ```no_run
+/// use painless_input::input_array_fixed;
+///
+/// let point: Vec<f64> = input_array_fixed("Enter x, y and z: ", 3);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_array_fixed, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('1'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('2'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (nums, output) = source.run(|| input_array_fixed::<i32>("Enter 2 numbers: ", 2));
+/// assert_eq!(nums, vec![1, 2]);
+/// assert!(output.contains("[_, _]"));
+/// assert!(output.contains("[1, _]"));
+/// assert!(output.contains("[1, 2]"));
+/// ```
+pub fn input_array_fixed<T>(input_str: &str, n: usize) -> Vec<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    event::with_terminal(|source, out| input_array_fixed_internal(source, out, input_str, n, &Theme::default()))
+}
+
+/// Everything about a text input other than its prompt
+struct InputConfig<'a, T> {
+    /// Checked once the input parses, the error message is shown if it fails
+    validation: Option<ValidationFn<T>>,
+    /// Checked after `validation`, with the text that was typed as well as the parsed value
+    raw_validation: Option<RawValidationFn<T>>,
+    /// Checked last, can accept the value with a warning instead of only accepting or rejecting it
+    warn_validation: Option<WarnValidationFn<T>>,
+    /// Whether the user can press Esc to back out of the prompt
+    cancelable: bool,
+    /// Shown in place of each character typed
+    mask: Option<char>,
+    /// The most graphemes that can be typed
+    max_len: Option<usize>,
+    /// Chars it returns false for are dropped as they're typed
+    allow: Option<Box<dyn Fn(char) -> bool + 'a>>,
+    /// How long the user has to submit the whole input
+    timeout: Option<Duration>,
+    /// Earlier lines that can be recalled with Up and Down, the submitted line is added to it
+    history: Option<&'a mut Vec<String>>,
+    /// Returned if nothing is typed
+    default: Option<T>,
+    /// Submits the input, Enter does nothing if it's another key
+    submit_key: SubmitKey,
+    /// Whether whitespace around the input is trimmed off before it's parsed
+    trim: bool,
+    /// How many times the input can fail to parse or validate before the prompt gives up
+    max_retries: Option<usize>,
+    /// Told about keystrokes, failed validations and the final value, for logging
+    on_event: Option<OnEventFn<'a>>,
+    /// Text the input starts with, which can be edited like anything typed
+    initial: Option<String>,
+    /// Shown after the input, like a unit, and moved along as it gets longer or shorter
+    suffix: Option<String>,
+    /// Whether the suffix is left after the submitted input, instead of being cleared
+    keep_suffix: bool,
+}
+
+impl<T> InputConfig<'_, T> {
+    fn new() -> Self {
+        Self {
+            validation: None,
+            raw_validation: None,
+            warn_validation: None,
+            cancelable: false,
+            mask: None,
+            max_len: None,
+            allow: None,
+            timeout: None,
+            history: None,
+            default: None,
+            submit_key: SubmitKey::Enter,
+            trim: true,
+            max_retries: None,
+            on_event: None,
+            initial: None,
+            suffix: None,
+            keep_suffix: false,
+        }
+    }
+}
+
+/// Returns `Error::Cancelled` if `config.cancelable` is set and the user pressed Esc, `Error::TimedOut` if `config.timeout` ran out,
+/// `Error::TooManyAttempts` once the input has been rejected `config.max_retries` times, or `Error::InputClosed` if piped stdin ran out.
+fn input_internal<T>(
+    source: &mut dyn EventSource,
+    out: &mut Output,
+    input_str: &str,
+    config: InputConfig<T>,
+    theme: &Theme,
+) -> Result<T, Error>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    // The timeout is for the whole prompt, it isn't reset by key presses
+    let deadline = config.timeout.map(|timeout| Instant::now() + timeout);
+
+    let mut state = InputState::new(out, input_str, config, theme);
+
+    loop {
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            // Everything drawn so far has to be shown before waiting
+            out.flush().unwrap();
+
+            if !source.poll(remaining).unwrap() {
+                return Err(state.time_out(out));
+            }
+        }
+
+        let key_event = read_event(source, out);
+
+        // Piped stdin sends Ctrl+D once it runs out, after which there's nothing left to answer with
+        if let crossterm::event::Event::Key(key) = &key_event {
+            if !source.is_interactive() && key.code == crossterm::event::KeyCode::Char('d') && key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) {
+                return Err(state.close(out));
+            }
+        }
+
+        if let Some(res) = state.handle(out, theme, key_event) {
+            return res;
+        }
+    }
+}
+
+/// What a text input has to keep track of between events, so the same handling can be driven by blocking reads or by awaiting events
+struct InputState<'a, T> {
+    validation_closure: ValidationFn<T>,
+    raw_validation: Option<RawValidationFn<T>>,
+    warn_validation: Option<WarnValidationFn<T>>,
+    cancelable: bool,
+    mask: Option<char>,
+    max_len: Option<usize>,
+    allow: Option<Box<dyn Fn(char) -> bool + 'a>>,
+    history: Option<&'a mut Vec<String>>,
+    default: Option<T>,
+    submit_key: SubmitKey,
+    trim: bool,
+    max_retries: Option<usize>,
+    on_event: Option<OnEventFn<'a>>,
+    /// How many times the input has failed to parse or validate so far
+    failures: usize,
+    /// Width of the prompt, which the typed text starts after
+    prompt_width: usize,
+    /// Whether masked input has been toggled to show what was actually typed
+    revealed: bool,
+    // This is used to show error message and delete it correctly when user enters something
+    current_err_msg_len: usize,
+    /// Whether the error message is on the row below the input, instead of after it
+    error_below: bool,
+    suffix: Option<String>,
+    keep_suffix: bool,
+    /// Width of the suffix and the `(3/16)` counter shown after the input when there's a `max_len`, or 0 if neither is shown
+    counter_len: usize,
+    input: String,
+    /// Byte index into `input` the cursor is at, always on a grapheme boundary
+    cursor_index: usize,
+    // Which history line is recalled, and what was typed before going into the history
+    history_index: Option<usize>,
+    draft: String,
+}
+
+impl<'a, T> InputState<'a, T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    /// Print the prompt and start with nothing typed
+    fn new(out: &mut Output, input_str: &str, config: InputConfig<'a, T>, theme: &Theme) -> Self {
+        let InputConfig { validation, raw_validation, warn_validation, cancelable, mask, max_len, allow, timeout: _, history, default, submit_key, trim, max_retries, on_event, initial, suffix, keep_suffix } = config;
+
+        let mut prompt_width = 0;
+        print_wrapping(out, input_str, &mut prompt_width, str::to_string);
+
+        let validation_closure = if let Some(value) = validation {
+            value
+        } else {
+            Box::new(|_: &_| Ok(()))
+        };
+
+        let mut state = Self {
+            validation_closure,
+            raw_validation,
+            warn_validation,
+            cancelable,
+            mask,
+            max_len,
+            allow,
+            history,
+            default,
+            submit_key,
+            trim,
+            max_retries,
+            on_event,
+            failures: 0,
+            prompt_width,
+            revealed: false,
+            current_err_msg_len: 0,
+            error_below: false,
+            suffix,
+            keep_suffix,
+            counter_len: 0,
+            input: String::new(),
+            cursor_index: 0,
+            history_index: None,
+            draft: String::new(),
+        };
+
+        // The initial text is drawn with the cursor at the end of it, ready to be edited
+        if let Some(initial) = initial {
+            state.input = pasted_chars(&initial).collect();
+            state.cursor_index = state.input.len();
+
+            let mut offset = state.prompt_width;
+            print_wrapping(out, &state.shown(&state.input), &mut offset, str::to_string);
+        }
+
+        state.draw_counter(out, theme);
+
+        state
+    }
+
+    /// Clean up the error message and whatever was typed after the timeout ran out, leaving just the prompt
+    fn time_out(&mut self, out: &mut Output) -> Error {
+        self.clear_counter(out);
+        self.clear_error(out);
+        self.clear_input(out);
+
+        self.emit(PromptEvent::TimedOut);
+
+        Error::TimedOut
+    }
+
+    /// Clean up the error message of the last line after piped stdin ran out, leaving just the prompt
+    fn close(&mut self, out: &mut Output) -> Error {
+        self.clear_counter(out);
+        self.clear_error(out);
+        self.clear_input(out);
+
+        Error::InputClosed
+    }
+
+    /// Pass `event` on to the `on_event` callback, if there is one
+    fn emit(&self, event: PromptEvent) {
+        if let Some(on_event) = &self.on_event {
+            on_event(event);
+        }
+    }
+
+    /// Count a rejected input, returning the error to finish with if that was the last one allowed.
+    /// The input has already been cleared, so only the prompt is left behind.
+    fn reject(&mut self, error_msg: &str) -> Option<Error> {
+        self.emit(PromptEvent::ValidationFailed(error_msg.to_string()));
+
+        self.failures += 1;
+
+        if self.max_retries.is_some_and(|max_retries| self.failures >= max_retries) {
+            self.emit(PromptEvent::TooManyAttempts);
+
+            Some(Error::TooManyAttempts)
+        } else {
+            None
+        }
+    }
+
+    /// The mask the input is drawn with, which is none once it's been revealed
+    fn shown_mask(&self) -> Option<char> {
+        if self.revealed {
+            None
+        } else {
+            self.mask
+        }
+    }
+
+    /// How many columns into the line the cursor is, counting from the start of the prompt.
+    /// The line wraps onto more rows if it's wider than the terminal, so this can be more than the width.
+    fn cursor(&self) -> usize {
+        self.prompt_width + displayed_len(&self.input[..self.cursor_index], self.shown_mask())
+    }
+
+    /// How many columns into the line the end of the input is
+    fn end(&self) -> usize {
+        self.prompt_width + displayed_len(&self.input, self.shown_mask())
+    }
+
+    /// `text` the way it's drawn, which is a mask character for each char unless it's been revealed
+    fn shown(&self, text: &str) -> String {
+        match self.shown_mask() {
+            Some(mask) => mask.to_string().repeat(text.chars().count()),
+            None => text.to_string(),
+        }
+    }
+
+    /// Move the cursor to byte `index` of the input
+    fn move_cursor(&mut self, out: &mut Output, index: usize) {
+        let from = self.cursor();
+        self.cursor_index = index;
+
+        if self.cursor() < from {
+            move_back(out, from, self.cursor());
+        } else {
+            move_forward(out, from, self.cursor());
+        }
+    }
+
+    /// Add `c` at the cursor and move the cursor past it, returning false if it can't be typed.
+    /// It isn't drawn, `redraw_from` does that once everything has been added.
+    fn insert_char(&mut self, c: char) -> bool {
+        if self.allow.as_ref().is_some_and(|allow| !allow(c)) {
+            return false;
+        }
+
+        self.input.insert(self.cursor_index, c);
+
+        // Ignore the char if it would start a grapheme past the limit, chars that join onto another grapheme are still allowed
+        if self.max_len.is_some_and(|max_len| self.input.graphemes(true).count() > max_len) {
+            self.input.remove(self.cursor_index);
+            return false;
+        }
+
+        self.cursor_index += c.len_utf8();
+
+        // Masked input only passes on the mask
+        self.emit(PromptEvent::CharTyped(self.shown_mask().unwrap_or(c)));
+
+        true
+    }
+
+    /// Draw what was inserted at byte `old_index` and the rest of the input after it, with the cursor drawn at `old_index` to begin with.
+    /// It's redrawn from the start of the grapheme the first inserted char ended up in, as it can join onto the one before it, like a skin tone modifier.
+    fn redraw_inserted(&self, out: &mut Output, old_index: usize, old_end: usize) {
+        let start = self.input.grapheme_indices(true)
+            .map(|(index, _)| index)
+            .take_while(|index| *index <= old_index)
+            .last()
+            .unwrap_or(0);
+
+        if start < old_index {
+            let old_cursor = self.prompt_width + displayed_len(&self.input[..old_index], self.shown_mask());
+            move_back(out, old_cursor, self.prompt_width + displayed_len(&self.input[..start], self.shown_mask()));
+        }
+
+        self.redraw_from(out, start, old_end);
+    }
+
+    /// Redraw the input from byte `start` to its end, with the cursor drawn at `start` to begin with.
+    /// Anything left on the screen up to `old_end` is cleared if the input got shorter, and then the cursor is put back where it belongs.
+    fn redraw_from(&self, out: &mut Output, start: usize, old_end: usize) {
+        let mut start = start;
+        let mut offset = self.prompt_width + displayed_len(&self.input[..start], self.shown_mask());
+
+        // Terminals that reorder right-to-left text can show any part of the line somewhere else, so the whole of it is redrawn instead
+        if start > 0 && self.shown_mask().is_none() && contains_rtl(&self.input) {
+            move_back(out, offset, self.prompt_width);
+
+            start = 0;
+            offset = self.prompt_width;
+        }
+
+        print_wrapping(out, &self.shown(&self.input[start..]), &mut offset, str::to_string);
+
+        if old_end > offset {
+            print_wrapping(out, &" ".repeat(old_end - offset), &mut offset, str::to_string);
+        }
+
+        if offset != self.cursor() {
+            move_back(out, offset, self.cursor());
+        }
+    }
+
+    /// Clear what's been typed off the screen, leaving `input` as it is and the cursor at the start of it
+    fn clear_input(&mut self, out: &mut Output) {
+        if self.input.is_empty() {
+            return;
+        }
+
+        self.move_cursor(out, 0);
+        clear_right_wrapping(out, self.prompt_width, displayed_len(&self.input, self.shown_mask()));
+    }
+
+    /// Show an error message after the cursor, or on the row below if the theme says so, leaving the cursor where it is so typing clears it
+    fn show_error(&mut self, out: &mut Output, theme: &Theme, error_msg: &str) {
+        ring_bell(out, theme);
+
+        self.error_below = theme.error_below;
+
+        if self.error_below {
+            let width = out.width().max(1) as usize;
+            let error_msg = truncate_to_width(error_msg, width - 1);
+
+            crossterm::queue!(
+                out,
+                crossterm::style::Print("\r\n"),
+                crossterm::style::PrintStyledContent(theme.error(&error_msg)),
+                crossterm::cursor::MoveUp(1),
+                crossterm::cursor::MoveToColumn((self.cursor() % width) as u16)
+            )
+                .unwrap();
+
+            self.current_err_msg_len = display_width(&error_msg) as usize;
+        } else {
+            let mut offset = self.cursor();
+            print_wrapping(out, error_msg, &mut offset, |row_text| theme.error(row_text).to_string());
+            move_back(out, offset, self.cursor());
+
+            self.current_err_msg_len = display_width(error_msg) as usize;
+        }
+    }
+
+    /// Show the suffix and how many characters have been typed out of `max_len` after the input, unless an error message is there.
+    /// The cursor is left where it was, so it never goes past the end of the input into the suffix.
+    fn draw_counter(&mut self, out: &mut Output, theme: &Theme) {
+        if self.suffix.is_none() && self.max_len.is_none() {
+            return;
+        }
+
+        if self.current_err_msg_len > 0 {
+            return;
+        }
+
+        let suffix = self.suffix.as_deref().unwrap_or("");
+        let counter = self.max_len.map_or(String::new(), |max_len| format!(" ({}/{})", self.input.graphemes(true).count(), max_len));
+
+        let mut offset = self.end();
+        move_forward(out, self.cursor(), offset);
+        print_wrapping(out, suffix, &mut offset, str::to_string);
+        print_wrapping(out, &counter, &mut offset, |row_text| theme.hint(row_text).to_string());
+        move_back(out, offset, self.cursor());
+
+        self.counter_len = display_width(suffix) as usize + counter.width();
+    }
+
+    fn clear_counter(&mut self, out: &mut Output) {
+        if self.counter_len > 0 {
+            move_forward(out, self.cursor(), self.end());
+            clear_right_wrapping(out, self.end(), self.counter_len);
+
+            if self.cursor() != self.end() {
+                move_back(out, self.end(), self.cursor());
+            }
+
+            self.counter_len = 0;
+        }
+    }
+
+    fn clear_error(&mut self, out: &mut Output) {
+        if self.current_err_msg_len > 0 {
+            if self.error_below {
+                // Moving straight down and back up keeps the cursor's column
+                crossterm::queue!(
+                    out,
+                    crossterm::cursor::MoveDown(1),
+                    crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine),
+                    crossterm::cursor::MoveUp(1)
+                )
+                    .unwrap();
+            } else {
+                clear_right_wrapping(out, self.cursor(), self.current_err_msg_len);
+            }
+
+            self.current_err_msg_len = 0;
+        }
+    }
+
+    /// Handle one event, returning the result once the prompt is finished
+    fn handle(&mut self, out: &mut Output, theme: &Theme, key_event: crossterm::event::Event) -> Option<Result<T, Error>> {
+        // The counter is taken off while the input is edited and put back after, so it's left off once the prompt is finished
+        self.clear_counter(out);
+
+        let res = self.handle_event(out, theme, key_event);
+
+        if res.is_none() {
+            self.draw_counter(out, theme);
+        }
+
+        res
+    }
+
+    fn handle_event(&mut self, out: &mut Output, theme: &Theme, key_event: crossterm::event::Event) -> Option<Result<T, Error>> {
+        // A paste arrives all at once, and is added as if each character was typed
+        if let crossterm::event::Event::Paste(text) = &key_event {
+            self.clear_error(out);
+
+            let old_end = self.end();
+            let old_index = self.cursor_index;
+
+            for c in pasted_chars(text) {
+                self.insert_char(c);
+            }
+
+            self.redraw_inserted(out, old_index, old_end);
+        }
+
+        if let crossterm::event::Event::Key(key) = key_event {
+            if key.kind != crossterm::event::KeyEventKind::Press {
+                return None;
+            }
+
+            // Ctrl+C cancels the prompt if it can be cancelled, otherwise it exits the program
+            let code = if is_interrupt(&key) {
+                if !self.cancelable {
+                    interrupt(out);
+                }
+
+                crossterm::event::KeyCode::Esc
+            } else if self.submit_key.matches(&key) {
+                crossterm::event::KeyCode::Enter
+            } else if key.code == crossterm::event::KeyCode::Enter {
+                return None;
+            } else if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) && key.code == crossterm::event::KeyCode::Char('a') {
+                // Ctrl+A and Ctrl+E are the readline keys for Home and End
+                crossterm::event::KeyCode::Home
+            } else if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) && key.code == crossterm::event::KeyCode::Char('e') {
+                crossterm::event::KeyCode::End
+            } else {
+                key.code
+            };
+
+            match code {
+                crossterm::event::KeyCode::Enter => {
+                    // An error left from the last try is cleared, so it isn't left behind or drawn over by the next one
+                    self.clear_error(out);
+
+                    // Anything shown after the input, like a warning, goes after the end of it
+                    self.move_cursor(out, self.input.len());
+
+                    // Masked input is a secret like a password, where the spaces are part of it
+                    let text = if self.trim && self.mask.is_none() { self.input.trim() } else { &self.input };
+
+                    if text.is_empty() {
+                        if let Some(default) = self.default.take() {
+                            return Some(Ok(self.submit(out, default)));
+                        }
+                    }
+
+                    let parsed_input = text.parse::<T>();
+
+                    if let Ok(res) = parsed_input {
+                        // Everything drawn so far is shown first, a slow validation might draw a spinner with `with_spinner`
+                        out.flush().unwrap();
+
+                        let validation_res = (self.validation_closure)(&res)
+                            .and_then(|_| self.raw_validation.as_ref().map_or(Ok(()), |raw_validation| raw_validation(&self.input, &res)))
+                            .map_or_else(Validation::Err, |_| self.warn_validation.as_ref().map_or(Validation::Ok, |warn_validation| warn_validation(&res)));
+
+                        match validation_res {
+                            Validation::Ok => return Some(Ok(self.submit(out, res))),
+                            Validation::Warn(warning) => {
+                                let res = self.submit(out, res);
+
+                                // The value is accepted, the warning is left next to it
+                                crossterm::queue!(
+                                    out,
+                                    crossterm::style::Print("  "),
+                                    crossterm::style::PrintStyledContent(theme.warning(warning))
+                                )
+                                    .unwrap();
+
+                                return Some(Ok(res));
+                            }
+                            Validation::Err(error_msg) => {
+                                // If input is not valid, show a red bg white text error message after clearing the length of the current_input
+                                self.clear_input(out);
+                                self.input.clear();
+                                self.cursor_index = 0;
+
+                                if let Some(err) = self.reject(&error_msg) {
+                                    return Some(Err(err));
+                                }
+
+                                self.show_error(out, theme, &error_msg);
+                            }
+                        }
+                    } else {
+                        // If input is not valid, show a red bg white text error message after clearing the length of the current_input
+                        self.clear_input(out);
+
+                        // Don't leak masked input in the error message
+                        let error_msg = format!("Invalid input: '{}'; try again", self.shown(&self.input));
+
+                        self.input.clear();
+                        self.cursor_index = 0;
+
+                        if let Some(err) = self.reject(&error_msg) {
+                            return Some(Err(err));
+                        }
+
+                        self.show_error(out, theme, &error_msg);
+                    }
+                }
+                // Ctrl+R toggles between showing the mask and what was actually typed
+                crossterm::event::KeyCode::Char('r') if self.mask.is_some() && key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) => {
+                    // The old render is cleared first, as the typed text and the mask can be different widths
+                    let cursor_index = self.cursor_index;
+
+                    self.clear_error(out);
+                    self.clear_input(out);
+
+                    self.revealed = !self.revealed;
+                    self.cursor_index = cursor_index;
+
+                    self.redraw_from(out, 0, self.prompt_width);
+                }
+                // Ctrl+U deletes everything before the cursor
+                crossterm::event::KeyCode::Char('u') if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) => {
+                    let old_end = self.end();
+                    let before_cursor = self.cursor_index;
+
+                    self.move_cursor(out, 0);
+                    self.input.replace_range(..before_cursor, "");
+
+                    self.redraw_from(out, 0, old_end);
+
+                    if before_cursor > 0 {
+                        self.emit(PromptEvent::Deleted);
+                    }
+                }
+                // Ctrl+K deletes everything from the cursor to the end
+                crossterm::event::KeyCode::Char('k') if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) => {
+                    let old_end = self.end();
+
+                    if self.cursor_index < self.input.len() {
+                        self.emit(PromptEvent::Deleted);
+                    }
+
+                    self.input.truncate(self.cursor_index);
+
+                    self.redraw_from(out, self.cursor_index, old_end);
+                }
+                crossterm::event::KeyCode::Char(c) => {
+                    self.clear_error(out);
+
+                    let old_end = self.end();
+                    let old_index = self.cursor_index;
+
+                    if self.insert_char(c) {
+                        self.redraw_inserted(out, old_index, old_end);
+                    }
+                }
+                crossterm::event::KeyCode::Backspace => {
+                    // Deletes the grapheme before the cursor
+                    if let Some((start, _)) = self.input[..self.cursor_index].grapheme_indices(true).next_back() {
+                        let old_end = self.end();
+                        let end = self.cursor_index;
+
+                        self.move_cursor(out, start);
+                        self.input.replace_range(start..end, "");
+
+                        self.redraw_from(out, start, old_end);
+
+                        self.emit(PromptEvent::Deleted);
+                    }
+                }
+                crossterm::event::KeyCode::Delete => {
+                    // Deletes the grapheme after the cursor, the cursor stays where it is
+                    if let Some(grapheme) = self.input[self.cursor_index..].graphemes(true).next() {
+                        let old_end = self.end();
+                        let start = self.cursor_index;
+
+                        self.input.replace_range(start..start + grapheme.len(), "");
+
+                        self.redraw_from(out, start, old_end);
+
+                        self.emit(PromptEvent::Deleted);
+                    }
+                }
+                crossterm::event::KeyCode::Left => {
+                    if let Some((start, _)) = self.input[..self.cursor_index].grapheme_indices(true).next_back() {
+                        self.move_cursor(out, start);
+                    }
+                }
+                crossterm::event::KeyCode::Right => {
+                    if let Some(grapheme) = self.input[self.cursor_index..].graphemes(true).next() {
+                        self.move_cursor(out, self.cursor_index + grapheme.len());
+                    }
+                }
+                crossterm::event::KeyCode::Home => self.move_cursor(out, 0),
+                crossterm::event::KeyCode::End => self.move_cursor(out, self.input.len()),
+                crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Down => {
+                    let history = self.history.as_deref()?;
+
+                    let recalled = match (code, self.history_index) {
+                        (crossterm::event::KeyCode::Up, None) if !history.is_empty() => {
+                            self.draft = self.input.clone();
+                            Some(history.len() - 1)
+                        }
+                        (crossterm::event::KeyCode::Up, Some(index)) => Some(index.saturating_sub(1)),
+                        (crossterm::event::KeyCode::Down, Some(index)) if index + 1 < history.len() => Some(index + 1),
+                        // Going down past the newest line goes back to what was being typed
+                        (crossterm::event::KeyCode::Down, Some(_)) => None,
+                        _ => return None,
+                    };
+
+                    self.history_index = recalled;
+
+                    let line = match recalled {
+                        Some(index) => history[index].clone(),
+                        None => std::mem::take(&mut self.draft),
+                    };
+
+                    self.clear_error(out);
+
+                    // Replace the line, the recalled line is a copy so editing it leaves the history alone
+                    self.clear_input(out);
+
+                    self.input = line;
+                    self.cursor_index = self.input.len();
+
+                    self.redraw_from(out, 0, self.prompt_width);
+                }
+                crossterm::event::KeyCode::Esc if self.cancelable => {
+                    // Clean up the error message and whatever was typed, leaving just the prompt
+                    self.clear_error(out);
+                    self.clear_input(out);
+
+                    self.emit(PromptEvent::Cancelled);
+
+                    return Some(Err(Error::Cancelled));
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    /// Add the submitted line to the history, and hand back the value.
+    /// The cursor is at the end of the input, where the suffix is drawn again if it's kept.
+    fn submit(&mut self, out: &mut Output, res: T) -> T {
+        self.emit(PromptEvent::Submitted(self.shown(&self.input)));
+
+        if let Some(suffix) = self.suffix.as_deref().filter(|_| self.keep_suffix) {
+            let mut offset = self.end();
+            print_wrapping(out, suffix, &mut offset, str::to_string);
+        }
+
+        if let Some(history) = self.history.as_deref_mut() {
+            if !self.input.is_empty() {
+                history.push(std::mem::take(&mut self.input));
+            }
+        }
+
+        res
+    }
+}
+
+/// Everything about an array input other than its prompt
+struct ArrayConfig<'a, T> {
+    /// Checked once the whole array is entered, the array is started over if it fails
+    validation: Option<ValidationFn<Vec<T>>>,
+    /// Checked as each element is entered, only that element has to be typed again if it fails
+    element_validation: Option<ElementValidationFn<'a, T>>,
+    /// Whether Enter with nothing entered yet is refused, instead of returning an empty array
+    nonempty: bool,
+    /// Shown dimmed after the cursor and kept up to date as elements are added and removed, cleared once the array is finished
+    preview: Option<ArrayPreviewFn<'a, T>>,
+}
+
+impl<T> ArrayConfig<'_, T> {
+    fn new() -> Self {
+        Self {
+            validation: None,
+            element_validation: None,
+            nonempty: false,
+            preview: None,
+        }
+    }
+}
+
+fn input_array_internal<T>(
+    source: &mut dyn EventSource,
+    out: &mut Output,
+    input_str: &str,
+    config: ArrayConfig<'_, T>,
+    theme: &Theme,
+) -> Vec<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    let ArrayConfig { validation, element_validation, nonempty, preview } = config;
+
+    // Whether the preview is on screen after the cursor
+    let mut preview_shown = false;
+
+    crossterm::queue!(
+        out,
+        crossterm::style::Print(input_str),
+        crossterm::style::Print(&theme.array_open)
+    )
+        .unwrap();
+
+    // Input data like this
+    // First print [ and then ask for input
+    // Then print , and ask for input
+    // If enter is pressed without any input, it will stop
+    // After that print ]
+    // Example:
+    // [1, 2, 3, 4, 5]
+
+    let mut current_input = String::new();
+    let mut result = Vec::new();
+    let mut input_str_vec: Vec<String> = Vec::new();
+
+    // This is used to show error message and delete it correctly when user enters something
+    let mut current_err_msg_len = 0;
+
+    // Columns taken up by the separator, to clear it again
+    let separator_width = display_width(&theme.array_separator);
+
+    // Which entered element is being edited after going back to it with Left, `current_input` holds its new text while it is
+    let mut editing: Option<usize> = None;
+    // What was typed of the next element before going back, brought back once the edit is over
+    let mut draft = String::new();
+
+    let validation_closure = if let Some(value) = validation {
+        value
+    } else {
+        Box::new(|_: &_| Ok(()))
+    };
+
+    loop {
+        // The preview makes way for errors and for the line drawn while editing an element
+        if let Some(preview) = &preview {
+            if editing.is_none() && current_err_msg_len == 0 {
+                let preview_str = format!("  {}", preview(&result));
+
+                crossterm::queue!(
+                    out,
+                    crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine),
+                    crossterm::style::PrintStyledContent(theme.hint(&preview_str)),
+                    crossterm::cursor::MoveLeft(display_width(&preview_str))
+                )
+                    .unwrap();
+
+                preview_shown = true;
+            }
+        }
+
+        let key_event = read_event(source, out);
+
+        // Cleared before anything else is drawn, it's drawn again with the new elements before the next key
+        if preview_shown {
+            crossterm::queue!(out, crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)).unwrap();
+            preview_shown = false;
+        }
+
+        // A pasted array, or a row or column of a spreadsheet, is added as all of its elements at once
+        let pasted_elements = match &key_event {
+            crossterm::event::Event::Paste(text) if editing.is_none() => split_pasted_array(text, &theme.array_separator),
+            _ => None,
+        };
+
+        if let Some(elements) = pasted_elements {
+            if current_err_msg_len > 0 {
+                clear_right(out, current_err_msg_len as u16);
+                current_err_msg_len = 0;
+            }
+
+            // What's typed of the next element is the start of the first one pasted
+            clear_left(out, current_input.width() as u16);
+
+            let mut rejected = Vec::new();
+
+            for (i, element) in elements.into_iter().enumerate() {
+                let element = if i == 0 { std::mem::take(&mut current_input) + &element } else { element };
+
+                let valid = element.parse::<T>().ok().filter(|value| {
+                    element_validation.as_ref().is_none_or(|element_validation| element_validation(value, &result).is_ok())
+                });
+
+                // Elements that are no good are left out, the others are still added
+                match valid {
+                    Some(value) => {
+                        result.push(value);
+
+                        crossterm::queue!(out, crossterm::style::Print(&element), crossterm::style::Print(&theme.array_separator)).unwrap();
+
+                        input_str_vec.push(element);
+                    }
+                    None => rejected.push(format!("'{}'", element)),
+                }
+            }
+
+            if !rejected.is_empty() {
+                let error_msg = format!("Left out {}", rejected.join(", "));
+
+                error_display(out, theme, &error_msg, &mut current_err_msg_len);
+            }
+
+            continue;
+        }
+
+        // Any other paste is added to the current element as if each character was typed
+        if let crossterm::event::Event::Paste(text) = &key_event {
+            let text: String = pasted_chars(text).collect();
+
+            current_input.push_str(&text);
+
+            if editing.is_some() {
+                redraw_array_line(out, input_str, &input_str_vec, editing, &current_input, &draft, theme);
+                current_err_msg_len = 0;
+            } else {
+                if current_err_msg_len > 0 {
+                    clear_right(out, current_err_msg_len as u16);
+                    current_err_msg_len = 0;
+                }
+
+                crossterm::queue!(out, crossterm::style::Print(text)).unwrap();
+            }
+        }
+
+        if let crossterm::event::Event::Key(key) = key_event {
+            if key.kind != crossterm::event::KeyEventKind::Press {
+                continue;
+            }
+
+            if is_interrupt(&key) {
+                interrupt(out);
+            }
+
+            if let Some(index) = editing {
+                match key.code {
+                    crossterm::event::KeyCode::Enter => {
+                        if current_input.is_empty() {
+                            // Emptying an element and pressing Enter removes it
+                            result.remove(index);
+                            input_str_vec.remove(index);
+                        } else {
+                            // Checked against the elements before it, just like when it was first entered
+                            let element_res = current_input.parse::<T>()
+                                .map_err(|_| format!("Invalid input: '{}'; try again", current_input))
+                                .and_then(|value| match &element_validation {
+                                    Some(element_validation) => element_validation(&value, &result[..index]).map(|_| value),
+                                    None => Ok(value),
+                                });
+
+                            match element_res {
+                                Ok(value) => {
+                                    result[index] = value;
+                                    input_str_vec[index] = current_input.clone();
+                                }
+                                Err(error_msg) => {
+                                    // The element goes back to what it was, with the error shown at the end of the line
+                                    current_input = input_str_vec[index].clone();
+
+                                    let column = redraw_array_line(out, input_str, &input_str_vec, editing, &current_input, &draft, theme);
+                                    let end = array_line_width(input_str, &input_str_vec, &draft, theme);
+
+                                    crossterm::queue!(out, crossterm::cursor::MoveToColumn(end)).unwrap();
+                                    error_display(out, theme, &error_msg, &mut current_err_msg_len);
+                                    crossterm::queue!(out, crossterm::cursor::MoveToColumn(column)).unwrap();
+
+                                    continue;
+                                }
+                            }
+                        }
+
+                        // Back to typing the next element
+                        editing = None;
+                        current_input = std::mem::take(&mut draft);
+                    }
+                    // Any change not saved with Enter is dropped
+                    crossterm::event::KeyCode::Left if index > 0 => {
+                        editing = Some(index - 1);
+                        current_input = input_str_vec[index - 1].clone();
+                    }
+                    crossterm::event::KeyCode::Right => {
+                        if index + 1 < input_str_vec.len() {
+                            editing = Some(index + 1);
+                            current_input = input_str_vec[index + 1].clone();
+                        } else {
+                            editing = None;
+                            current_input = std::mem::take(&mut draft);
+                        }
+                    }
+                    crossterm::event::KeyCode::Esc => {
+                        // The edit and the draft are dropped, then Esc finishes the array below
+                        editing = None;
+                        current_input.clear();
+                        draft.clear();
+                    }
+                    crossterm::event::KeyCode::Backspace => {
+                        pop_grapheme(&mut current_input);
+                    }
+                    crossterm::event::KeyCode::Char(c) => {
+                        current_input.push(c);
+                    }
+                    _ => {}
+                }
+
+                redraw_array_line(out, input_str, &input_str_vec, editing, &current_input, &draft, theme);
+                current_err_msg_len = 0;
+
+                if key.code != crossterm::event::KeyCode::Esc {
+                    continue;
+                }
+            }
+
+            match key.code {
+                crossterm::event::KeyCode::Enter | crossterm::event::KeyCode::Esc => {
+                    // Esc finishes the array like an empty Enter, so the element being typed is dropped first
+                    if key.code == crossterm::event::KeyCode::Esc && !current_input.is_empty() {
+                        clear_left(out, current_input.width() as u16);
+                        current_input.clear();
+                    }
+
+                    // If final element with no input
+                    if current_input.is_empty() {
+                        // If error message is shown, clear it
+                        if current_err_msg_len > 0 {
+                            clear_right(out, current_err_msg_len as u16);
+                            current_err_msg_len = 0;
+                        }
+
+                        if nonempty && input_str_vec.is_empty() {
+                            error_display(out, theme, "Enter at least one value", &mut current_err_msg_len);
+                            continue;
+                        }
+
+                        if !input_str_vec.is_empty() {
+                            // Clear the last separator from terminal
+                            clear_left(out, separator_width);
+                        }
+
+                        // This is the end so print the closing bracket
+                        crossterm::queue!(out, crossterm::style::Print(&theme.array_close))
+                            .unwrap();
+
+                        // Validation
+                        let validation_res = validation_closure(&result);
+                        if let Err(error_msg) = validation_res {
+                            // If input is not valid, show a red bg white text error message after clearing the length of the current_input
+
+                            // Start with the closing bracket
+                            let mut clear_amount = display_width(&theme.array_close) as usize;
+
+                            for (i, input_str) in input_str_vec.iter().enumerate() {
+                                clear_amount += input_str.width();
+
+                                // if not the last element, add the separator
+                                if i != input_str_vec.len() - 1 {
+                                    clear_amount += separator_width as usize;
+                                }
+                            }
+
+                            clear_left(out, clear_amount as u16);
+
+                            // crossterm::queue!(std::io::stdout(), crossterm::style::Print("["))
+                            //     .unwrap();
+
+                            error_display(out, theme, error_msg.as_str(), &mut current_err_msg_len);
+
+                            // Start the input again by resetting everything
+                            result.clear();
+                            input_str_vec.clear();
+                            current_input.clear();
+
+                            continue;
+                        } else {
+                            break;
+                        }
+                    }
+                    // If there is input
+                    else {
+                        // Add parsed input to result
+                        let parse_res = current_input.parse::<T>();
+
+                        if let Ok(value) = parse_res {
+                            let element_res = match &element_validation {
+                                Some(element_validation) => element_validation(&value, &result),
+                                None => Ok(()),
+                            };
+
+                            if let Err(error_msg) = element_res {
+                                // Only this element has to be typed again, the ones before it are kept
+                                clear_left(out, current_input.width() as u16);
+
+                                error_display(out, theme, error_msg.as_str(), &mut current_err_msg_len);
+
+                                current_input.clear();
+
+                                continue;
+                            }
+
+                            result.push(value);
+                        } else {
+                            // If input is not valid, show a red bg white text error message after clearing the length of the current_input
+                            clear_left(out, current_input.width() as u16);
+
+                            let error_msg =
+                                format!("Invalid input: '{}'; try again", current_input);
+
+                            error_display(out, theme, error_msg.as_str(), &mut current_err_msg_len);
+
+                            current_input.clear();
+
+                            continue;
+                        }
+
+                        // Add the current input to input_str_vec
+                        input_str_vec.push(current_input.clone());
+
+                        // Clear current_input
+                        current_input.clear();
+
+                        // Print the separator
+                        crossterm::queue!(out, crossterm::style::Print(&theme.array_separator))
+                            .unwrap();
+                    }
+                }
+                // Go back to the last element to edit it, keeping what's been typed of the next one
+                crossterm::event::KeyCode::Left if !input_str_vec.is_empty() => {
+                    let index = input_str_vec.len() - 1;
+
+                    draft = std::mem::take(&mut current_input);
+                    editing = Some(index);
+                    current_input = input_str_vec[index].clone();
+
+                    redraw_array_line(out, input_str, &input_str_vec, editing, &current_input, &draft, theme);
+                    current_err_msg_len = 0;
+                }
+                crossterm::event::KeyCode::Backspace => {
+                    if current_input.is_empty() {
+                        // This means the user wants to delete the last element
+                        // So we pop the last element from the result
+                        if !result.is_empty() {
+                            // If error message is shown, clear it
+                            if current_err_msg_len > 0 {
+                                clear_right(out, current_err_msg_len as u16);
+                                current_err_msg_len = 0;
+                            }
+
+                            result.pop();
+
+                            // clear the separator from terminal
+                            clear_left(out, separator_width);
+
+                            // delete the last input_str_vec and clear it from terminal
+                            let chars_to_clear = input_str_vec.pop().unwrap().width();
+
+                            clear_left(out, chars_to_clear as u16);
+                        }
+                    } else {
+                        // This means just delete the last character from current_input
+                        let grapheme = pop_grapheme(&mut current_input).unwrap();
+                        // Then delete from terminal
+                        clear_left(out, grapheme.width() as u16);
+                    }
+                }
+                crossterm::event::KeyCode::Char(c) => {
+                    // If error message is shown, clear it
+                    if current_err_msg_len > 0 {
+                        clear_right(out, current_err_msg_len as u16);
+                        current_err_msg_len = 0;
+                    }
+
+                    current_input.push(c);
+                    crossterm::queue!(out, crossterm::style::Print(c)).unwrap();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    result
+}
+
+/// The elements of a pasted block of text, if it's more than a single element.
+/// A block in brackets is split on `separator`, or on commas if it's only whitespace, like the JSON `[1, 2, 3]`, with quotes around elements taken off.
+/// Otherwise it's split on tabs and newlines, which is how spreadsheets copy a row or column. Empty elements are skipped.
+fn split_pasted_array(text: &str, separator: &str) -> Option<Vec<String>> {
+    let text = text.trim();
+
+    let elements: Vec<&str> = if let Some(inner) = text.strip_prefix('[').and_then(|text| text.strip_suffix(']')) {
+        let separator = match separator.trim() {
+            "" => ",",
+            separator => separator,
+        };
+
+        inner.split(separator).collect()
+    } else if text.contains(['\t', '\n']) {
+        text.split(['\t', '\n']).collect()
+    } else {
+        return None;
+    };
+
+    let elements = elements
+        .into_iter()
+        .map(|element| {
+            let element = element.trim();
+
+            element.strip_prefix('"').and_then(|element| element.strip_suffix('"')).unwrap_or(element)
+        })
+        .filter(|element| !element.is_empty())
+        .map(|element| pasted_chars(element).collect())
+        .collect();
+
+    Some(elements)
+}
+
+/// Width of an array input line with `elements` entered and `tail` typed after them, not counting the closing bracket
+fn array_line_width(input_str: &str, elements: &[String], tail: &str, theme: &Theme) -> u16 {
+    let elements_width: usize = elements.iter().map(|element| element.width() + display_width(&theme.array_separator) as usize).sum();
+
+    (display_width(input_str) as usize + display_width(&theme.array_open) as usize + elements_width + tail.width()) as u16
+}
+
+/// Draw the whole line of an array input again, with the element at `editing` showing `current_input` in the value style.
+/// `tail` is what's typed of the next element, which is `current_input` when nothing is being edited.
+/// Returns the column the cursor is left at, the end of the element being edited or of the line.
+fn redraw_array_line(out: &mut Output, input_str: &str, elements: &[String], editing: Option<usize>, current_input: &str, draft: &str, theme: &Theme) -> u16 {
+    let tail = if editing.is_some() { draft } else { current_input };
+
+    crossterm::queue!(out, crossterm::style::Print("\r"), crossterm::style::Print(input_str), crossterm::style::Print(&theme.array_open)).unwrap();
+
+    let mut column = display_width(input_str) as usize + display_width(&theme.array_open) as usize;
+    let mut cursor_column = None;
+
+    for (i, element) in elements.iter().enumerate() {
+        if editing == Some(i) {
+            crossterm::queue!(out, crossterm::style::PrintStyledContent(theme.value(current_input))).unwrap();
+
+            column += current_input.width();
+            cursor_column = Some(column);
+        } else {
+            crossterm::queue!(out, crossterm::style::Print(element)).unwrap();
+
+            column += element.width();
+        }
+
+        crossterm::queue!(out, crossterm::style::Print(&theme.array_separator)).unwrap();
+        column += display_width(&theme.array_separator) as usize;
+    }
+
+    column += tail.width();
+
+    crossterm::queue!(out, crossterm::style::Print(tail), crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)).unwrap();
+
+    let cursor_column = cursor_column.unwrap_or(column) as u16;
+
+    crossterm::queue!(out, crossterm::cursor::MoveToColumn(cursor_column)).unwrap();
+
+    cursor_column
+}
+
+fn input_array_delimited_internal<T>(
+    source: &mut dyn EventSource,
+    out: &mut Output,
+    input_str: &str,
+    delimiter: char,
+    theme: &Theme,
+) -> Vec<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    crossterm::queue!(out, crossterm::style::Print(input_str)).unwrap();
+
+    // Unlike input_array, the whole array is typed on one line and only split up on enter
+    // The line is kept when an element is invalid so it can be fixed
+
+    let mut input = String::new();
+
+    // This is used to show error message and delete it correctly when user enters something
+    let mut current_err_msg_len = 0;
+
+    loop {
+        let key_event = read_event(source, out);
+
+        // A paste arrives all at once, and is added as if each character was typed
+        if let crossterm::event::Event::Paste(text) = &key_event {
+            if current_err_msg_len > 0 {
+                clear_right(out, current_err_msg_len as u16);
+                current_err_msg_len = 0;
+            }
+
+            for c in pasted_chars(text) {
+                input.push(c);
+                crossterm::queue!(out, crossterm::style::Print(c)).unwrap();
+            }
+        }
+
+        if let crossterm::event::Event::Key(key) = key_event {
+            if key.kind != crossterm::event::KeyEventKind::Press {
+                continue;
+            }
+
+            if is_interrupt(&key) {
+                interrupt(out);
+            }
+
+            // If error message is shown, clear it before the line changes
+            if current_err_msg_len > 0 {
+                clear_right(out, current_err_msg_len as u16);
+                current_err_msg_len = 0;
+            }
+
+            match key.code {
+                crossterm::event::KeyCode::Enter => {
+                    let mut elements: Vec<&str> = input.split(delimiter).map(str::trim).collect();
+
+                    // Ignore empty elements at the end, like after a trailing delimiter
+                    while elements.last().is_some_and(|element| element.is_empty()) {
+                        elements.pop();
+                    }
+
+                    let mut result = Vec::new();
+                    let mut error_msg = None;
+
+                    for (i, element) in elements.iter().enumerate() {
+                        if let Ok(value) = element.parse::<T>() {
+                            result.push(value);
+                        } else {
+                            error_msg = Some(format!("  Invalid element {}: '{}'", i + 1, element));
+                            break;
+                        }
+                    }
+
+                    match error_msg {
+                        // Show the error after the line, keeping the input so it can be fixed
+                        Some(error_msg) => error_display(out, theme, error_msg.as_str(), &mut current_err_msg_len),
+                        None => return result,
+                    }
+                }
+                crossterm::event::KeyCode::Char(c) => {
+                    input.push(c);
+                    crossterm::queue!(out, crossterm::style::Print(c)).unwrap();
+                }
+                crossterm::event::KeyCode::Backspace => {
+                    if let Some(grapheme) = pop_grapheme(&mut input) {
+                        clear_left(out, grapheme.width() as u16);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn input_array_fixed_internal<T>(
+    source: &mut dyn EventSource,
+    out: &mut Output,
+    input_str: &str,
+    n: usize,
+    theme: &Theme,
+) -> Vec<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    let mut current_input = String::new();
+    let mut result = Vec::new();
+    let mut input_str_vec: Vec<String> = Vec::new();
+    let mut error_msg: Option<String> = None;
+
+    let mut first_iter = true;
+
+    // The whole line is redrawn on every change as the placeholders after the cursor move around
+    // Example while typing the second element:
+    // [1, 2_, _]
+    loop {
+        let mut update = false;
+
+        // If on the first iter, just print and don't wait for input
+        if first_iter {
+            first_iter = false;
+            update = true;
+        } else {
+            let key_event = read_event(source, out);
+
+            match key_event {
+                crossterm::event::Event::Key(key) if key.kind == crossterm::event::KeyEventKind::Press => {
+                    if is_interrupt(&key) {
+                        interrupt(out);
+                    }
+
+                    match key.code {
+                        // An element has to be typed, the input can't be finished early
+                        crossterm::event::KeyCode::Enter if !current_input.is_empty() => {
+                            if let Ok(value) = current_input.parse::<T>() {
+                                result.push(value);
+                                input_str_vec.push(current_input.clone());
+                                error_msg = None;
+                            } else {
+                                error_msg = Some(format!("Invalid input: '{}'; try again", current_input));
+                            }
+
+                            current_input.clear();
+                            update = true;
+                        }
+                        crossterm::event::KeyCode::Backspace => {
+                            if current_input.is_empty() {
+                                // Delete the previous element
+                                if input_str_vec.pop().is_some() {
+                                    result.pop();
+                                    error_msg = None;
+                                    update = true;
+                                }
+                            } else {
+                                pop_grapheme(&mut current_input);
+                                error_msg = None;
+                                update = true;
+                            }
+                        }
+                        crossterm::event::KeyCode::Char(c) => {
+                            current_input.push(c);
+                            error_msg = None;
+                            update = true;
+                        }
+                        _ => {}
+                    }
+                }
+                crossterm::event::Event::Paste(text) => {
+                    current_input.extend(pasted_chars(&text));
+                    error_msg = None;
+                    update = true;
+                }
+                _ => {}
+            }
+        }
+
+        if update {
+            let done = input_str_vec.len() == n;
+
+            let mut before_cursor = format!("{}{}{}", input_str, theme.array_open, input_str_vec.join(&theme.array_separator));
+            let mut after_cursor = String::new();
+
+            if !done {
+                if !input_str_vec.is_empty() {
+                    before_cursor.push_str(&theme.array_separator);
+                }
+
+                // The cursor sits on the placeholder of the current element until something is typed
+                if current_input.is_empty() {
+                    after_cursor.push('_');
+                } else {
+                    before_cursor.push_str(&current_input);
+                }
+
+                for _ in input_str_vec.len() + 1..n {
+                    after_cursor.push_str(&theme.array_separator);
+                    after_cursor.push('_');
+                }
+            }
+
+            after_cursor.push_str(&theme.array_close);
+
+            // Clear line
+            crossterm::queue!(out, crossterm::style::Print("\r")).unwrap();
+
+            crossterm::queue!(out, crossterm::style::Print(&before_cursor), crossterm::style::Print(&after_cursor)).unwrap();
+
+            let mut after_cursor_len = after_cursor.width();
+
+            if let Some(error_msg) = &error_msg {
+                crossterm::queue!(out, crossterm::style::Print("  "), crossterm::style::PrintStyledContent(theme.error(error_msg))).unwrap();
+                after_cursor_len += display_width(error_msg) as usize + 2;
+            }
+
+            crossterm::queue!(out, crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)).unwrap();
+
+            if done {
+                break;
+            }
+
+            // Move back to where the current element is typed
+            crossterm::queue!(out, crossterm::cursor::MoveLeft(after_cursor_len as u16)).unwrap();
+        }
+    }
+
+    result
+}
+
+fn input_autocomplete_internal(
+    source: &mut dyn EventSource,
+    out: &mut Output,
+    input_str: &str,
+    candidates: &[String],
+    theme: &Theme,
+) -> String {
+    let mut input = String::new();
+
+    // The candidates shown after the input when a completion is ambiguous
+    let mut hint: Vec<&String> = Vec::new();
+
+    // The candidates being cycled through with Tab and which one is shown
+    let mut cycle: Option<(Vec<&String>, usize)> = None;
+
+    crossterm::queue!(out, crossterm::style::Print(input_str)).unwrap();
+
+    loop {
+        let key_event = read_event(source, out);
+
+        match key_event {
+            crossterm::event::Event::Key(key) if key.kind == crossterm::event::KeyEventKind::Press => {
+                if is_interrupt(&key) {
+                    interrupt(out);
+                }
+
+                match key.code {
+                    crossterm::event::KeyCode::Enter => {
+                        break;
+                    }
+                    crossterm::event::KeyCode::Tab => {
+                        if let Some((matches, shown)) = &mut cycle {
+                            *shown = (*shown + 1) % matches.len();
+                            input = matches[*shown].clone();
+                        } else {
+                            let matches: Vec<&String> = candidates
+                                .iter()
+                                .filter(|candidate| candidate.starts_with(&input))
+                                .collect();
+
+                            if matches.is_empty() {
+                                continue;
+                            }
+
+                            let match_strs: Vec<&str> = matches.iter().map(|candidate| candidate.as_str()).collect();
+                            let prefix = common_prefix(&match_strs, false);
+
+                            if prefix.len() > input.len() {
+                                input = prefix;
+                            } else if matches.len() > 1 {
+                                // Nothing more can be completed, so start cycling through the matches
+                                input = matches[0].clone();
+                                cycle = Some((matches.clone(), 0));
+                            }
+
+                            hint = if matches.len() > 1 { matches } else { Vec::new() };
+                        }
+                    }
+                    crossterm::event::KeyCode::Backspace => {
+                        pop_grapheme(&mut input);
+                        hint.clear();
+                        cycle = None;
+                    }
+                    crossterm::event::KeyCode::Char(c) => {
+                        input.push(c);
+                        hint.clear();
+                        cycle = None;
+                    }
+                    _ => continue,
+                }
+            }
+            crossterm::event::Event::Paste(text) => {
+                input.extend(pasted_chars(&text));
+                hint.clear();
+                cycle = None;
+            }
+            _ => continue,
+        }
+
+            // Clear line
+            crossterm::queue!(out, crossterm::style::Print("\r")).unwrap();
+
+            crossterm::queue!(out, crossterm::style::Print(input_str), crossterm::style::Print(&input)).unwrap();
+
+            if hint.is_empty() {
+                crossterm::queue!(out, crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)).unwrap();
+            } else {
+                let hint_str = format!("  {}", hint.iter().map(|candidate| candidate.as_str()).collect::<Vec<_>>().join("  "));
+
+                crossterm::queue!(
+                    out,
+                    crossterm::style::PrintStyledContent(theme.hint(&hint_str)),
+                    crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine),
+                    crossterm::cursor::MoveLeft(display_width(&hint_str))
+                )
+                    .unwrap();
+            }
+    }
+
+    // Clear the hint
+    crossterm::queue!(out, crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)).unwrap();
+
+    input
+}
+
+/// Any of the `submit` keys submits the input
+fn input_multiline_internal(source: &mut dyn EventSource, out: &mut Output, input_str: &str, submit: &[SubmitKey]) -> String {
+    crossterm::queue!(out, crossterm::style::Print(input_str)).unwrap();
+
+    // Lines after the first are indented by the width of the prompt
+    let indent = display_width(input_str) as usize;
+
+    // Always has at least one line, the cursor is at the end of the last one
+    let mut lines = vec![String::new()];
+
+    loop {
+        let key_event = read_event(source, out);
+
+        match key_event {
+            crossterm::event::Event::Key(key) if key.kind == crossterm::event::KeyEventKind::Press => {
+                if is_interrupt(&key) {
+                    interrupt(out);
+                }
+
+                if submit.iter().any(|submit| submit.matches(&key)) {
+                    break;
+                }
+
+                match key.code {
+                    crossterm::event::KeyCode::Enter => multiline_insert(out, &mut lines, indent, '\n'),
+                    // Ctrl combinations that aren't the submit key aren't typed, AltGr chars come with Ctrl and Alt so they still are
+                    crossterm::event::KeyCode::Char(_) if key.modifiers == crossterm::event::KeyModifiers::CONTROL => {},
+                    crossterm::event::KeyCode::Char(c) => multiline_insert(out, &mut lines, indent, c),
+                    crossterm::event::KeyCode::Backspace => {
+                        let last_line = lines.last_mut().unwrap();
+
+                        if let Some(grapheme) = pop_grapheme(last_line) {
+                            clear_left(out, grapheme.width() as u16);
+                        } else if lines.len() > 1 {
+                            // Join onto the line above by moving back to the end of it, the empty line left behind is just indentation
+                            lines.pop();
+
+                            let column = indent + lines.last().unwrap().width();
+
+                            crossterm::queue!(out, crossterm::style::Print("\r"), crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)).unwrap();
+                            crossterm::queue!(out, crossterm::cursor::MoveUp(1), crossterm::cursor::MoveToColumn(column as u16)).unwrap();
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            crossterm::event::Event::Paste(text) => {
+                // Pasted newlines are kept as this input can have multiple lines
+                for c in text.chars().filter(|c| *c == '\n' || !c.is_control()) {
+                    multiline_insert(out, &mut lines, indent, c);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Add `c` to the end of a multiline input, starting a new line if it's a newline
+fn multiline_insert(out: &mut Output, lines: &mut Vec<String>, indent: usize, c: char) {
+    if c == '\n' {
+        lines.push(String::new());
+        crossterm::queue!(out, crossterm::style::Print("\r\n"), crossterm::style::Print(" ".repeat(indent))).unwrap();
+    } else {
+        lines.last_mut().unwrap().push(c);
+        crossterm::queue!(out, crossterm::style::Print(c)).unwrap();
+    }
+}
+
+fn input_char_internal(source: &mut dyn EventSource, out: &mut Output, input_str: &str, allow: impl Fn(char) -> bool, theme: &Theme) -> char {
+    crossterm::queue!(out, crossterm::style::Print(input_str)).unwrap();
+
+    // This is used to show error message and delete it correctly when another key is pressed
+    let mut current_err_msg_len = 0;
+
+    loop {
+        if let crossterm::event::Event::Key(key) = read_event(source, out) {
+            if key.kind != crossterm::event::KeyEventKind::Press {
+                continue;
+            }
+
+            if is_interrupt(&key) {
+                interrupt(out);
+            }
+
+            if let crossterm::event::KeyCode::Char(c) = key.code {
+                // Ctrl and Alt shortcuts aren't characters typed
+                if key.modifiers.intersects(crossterm::event::KeyModifiers::CONTROL | crossterm::event::KeyModifiers::ALT) {
+                    continue;
+                }
+
+                if current_err_msg_len > 0 {
+                    clear_right(out, current_err_msg_len as u16);
+                    current_err_msg_len = 0;
+                }
+
+                if allow(c) {
+                    crossterm::queue!(out, crossterm::style::Print(c)).unwrap();
+
+                    return c;
+                }
+
+                error_display(out, theme, &format!("'{}' isn't one of the choices", c), &mut current_err_msg_len);
+            }
+        }
+    }
+}
+
+fn password_confirm_internal(source: &mut dyn EventSource, out: &mut Output, prompt1: &str, prompt2: &str, theme: &Theme) -> String {
+    let width = out.width().max(1) as usize;
+    let mask = Some('*');
+
+    // Whether the mismatch error is on the row above the first prompt
+    let mut error_shown = false;
+
+    loop {
+        let first: String = input_internal(source, out, prompt1, InputConfig { mask, ..InputConfig::new() }, theme)
+            .expect("stdin closed before a valid answer was given");
+
+        crossterm::queue!(out, crossterm::style::Print("\r\n")).unwrap();
+
+        let second: String = input_internal(source, out, prompt2, InputConfig { mask, ..InputConfig::new() }, theme)
+            .expect("stdin closed before a valid answer was given");
+
+        if first == second {
+            return first;
+        }
+
+        ring_bell(out, theme);
+
+        // Go back up to the start of the first prompt, or the error above it, and clear everything from there down
+        let first_rows = (display_width(prompt1) as usize + displayed_len(&first, mask)) / width + 1;
+        let second_rows = (display_width(prompt2) as usize + displayed_len(&second, mask)) / width;
+        let rows_up = first_rows + second_rows + error_shown as usize;
+
+        crossterm::queue!(
+            out,
+            crossterm::cursor::MoveUp(rows_up as u16),
+            crossterm::cursor::MoveToColumn(0),
+            crossterm::terminal::Clear(crossterm::terminal::ClearType::FromCursorDown),
+            crossterm::style::PrintStyledContent(theme.error("Passwords don't match, try again")),
+            crossterm::style::Print("\r\n")
+        )
+            .unwrap();
+
+        error_shown = true;
+    }
+}
+
+fn error_display(out: &mut Output, theme: &Theme, error_msg: &str, error_len_var: &mut usize) {
+    ring_bell(out, theme);
+
+    // Red text and red underline by default
+    crossterm::queue!(
+        out,
+        crossterm::style::PrintStyledContent(theme.error(error_msg))
+    )
+        .unwrap();
+
+    // move cursor left
+    crossterm::queue!(
+        out,
+        crossterm::cursor::MoveLeft(display_width(error_msg))
+    )
+        .unwrap();
+
+    *error_len_var = display_width(error_msg) as usize;
+}
+
+
+/// Select an input from the user using arrow keys.
+/// The input will look like this
+/// Choose an option: [Test]⭥
+/// Click the up and down arrows (or k and j) to navigate, enter to submit
+/// The number keys 1 to 9 jump straight to the first nine options.
+///
+/// Options too wide for the terminal are cut short with a `…`, the returned index is still that of the full option.
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{select_input, VecEventSource};
+///
+/// let long_path = format!("/home/user/{}", "very_long_directory_name/".repeat(5));
+/// let options = [long_path.as_str(), "/tmp"];
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (index, output) = source.run(|| select_input("Folder: ", &options).unwrap());
+/// assert_eq!(index, 0);
+/// assert!(output.contains("…]"));
+/// assert!(!output.contains(&long_path));
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{select_input, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('3'))),
+///     // Out of range, so ignored
+///     Event::Key(KeyEvent::from(KeyCode::Char('9'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (index, _) = source.run(|| select_input("Pick: ", &["a", "b", "c", "d"]).unwrap());
+/// assert_eq!(index, 2);
+/// ```
+/// Returns `Error::NoOptions` if `options` is empty, which can happen when they're built up at runtime, instead of there being nothing to choose.
+/// ```
+/// use painless_input::{select_input, Error, VecEventSource};
+///
+/// let options: Vec<String> = Vec::new();
+///
+/// let (res, output) = VecEventSource::new(vec![]).run(|| select_input("Pick: ", &options));
+/// assert!(matches!(res, Err(Error::NoOptions)));
+/// // Nothing is drawn
+/// assert!(!output.contains("Pick"));
+/// ```
+pub fn select_input<T>(input_str: &str, options: &[T]) -> Result<usize, Error>
+    where T: Display
+{
+    event::with_terminal(|source, out| select_internal(source, out, input_str, options, SelectConfig::new(options.len()), &Theme::default()))
+}
+
+/// Same as `select_input`, but drawn using `theme`.
+/// Panics if `options` is empty.
+pub fn select_input_with_theme<T>(input_str: &str, options: &[T], theme: &Theme) -> usize
+    where T: Display
+{
+    event::with_terminal(|source, out| select_internal(source, out, input_str, options, SelectConfig::new(options.len()), theme))
+        .expect("options must not be empty")
+}
+
+/// Same as `select_input`, but the user can press Esc to back out of the selection, in which case `None` is returned.
+/// Panics if `options` is empty.
+pub fn select_optional<T>(input_str: &str, options: &[T]) -> Option<usize>
+    where T: Display
+{
+    match event::with_terminal(|source, out| select_internal(source, out, input_str, options, SelectConfig { cancelable: true, ..SelectConfig::new(options.len()) }, &Theme::default())) {
+        Ok(index) => Some(index),
+        Err(Error::Cancelled) => None,
+        Err(_) => panic!("options must not be empty"),
+    }
+}
+
+/// Same as `select_input`, but the selection wraps around.
+/// Pressing up on the first option goes to the last one, and pressing down on the last option goes back to the first.
+/// Panics if `options` is empty.
+pub fn select_input_wrapping<T>(input_str: &str, options: &[T]) -> usize
+    where T: Display
+{
+    event::with_terminal(|source, out| select_internal(source, out, input_str, options, SelectConfig { wrap: true, ..SelectConfig::new(options.len()) }, &Theme::default()))
+        .expect("options must not be empty")
+}
+
+/// Same as `select_input`, but options marked in `disabled` can't be chosen and are skipped over when navigating.
+/// Useful for separators or choices that aren't available right now.
+/// ## Example
+/// ```no_run
+/// use painless_input::select_input_with_disabled;
+///
+/// let index = select_input_with_disabled("Save as: ", &["PNG", "JPEG", "GIF (unavailable)"], &[false, false, true]);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{select_input_with_disabled, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     // Skips over both disabled options
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (index, _) = source.run(|| select_input_with_disabled("Pick: ", &["a", "b", "c", "d"], &[false, true, true, false]));
+/// assert_eq!(index, 3);
+/// ```
+/// ## Panics
+/// Panics if `options` is empty, if `disabled` doesn't have one entry per option, or if every option is disabled.
+pub fn select_input_with_disabled<T>(input_str: &str, options: &[T], disabled: &[bool]) -> usize
+    where T: Display
+{
+    assert_eq!(disabled.len(), options.len(), "disabled must have one entry per option");
+    assert!(options.is_empty() || disabled.contains(&false), "at least one option must be enabled");
+
+    let config = SelectConfig {
+        disabled: disabled.to_vec(),
+        ..SelectConfig::new(options.len())
+    };
+
+    event::with_terminal(|source, out| select_internal(source, out, input_str, options, config, &Theme::default()))
+        .expect("options must not be empty")
+}
+
+/// Same as `select_input`, but each option comes with a description shown dimmed under the prompt while it's the current choice.
+/// The description is cleared once an option is chosen.
+/// ## Example
+/// ```no_run
+/// use painless_input::select_with_hints;
+///
+/// let index = select_with_hints("Build profile: ", &[
+///     ("dev", "Fast to compile, slow to run"),
+///     ("release", "Slow to compile, fast to run"),
+/// ]);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{select_with_hints, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (index, output) = source.run(|| select_with_hints("Profile: ", &[("dev", "Fast to compile"), ("release", "Fast to run")]));
+/// assert_eq!(index, 1);
+/// assert!(output.contains("Fast to compile"));
+/// assert!(output.contains("Fast to run"));
+/// // The hint row is cleared at the end
+/// assert!(output.ends_with("\r\n\x1b[2K\x1b[1A\x1b[20G\x1b[?25h\x1b[0m\x1b[?25h"));
+/// ```
+/// ## Panics
+/// Panics if `options` is empty.
+pub fn select_with_hints<T>(input_str: &str, options: &[(T, &str)]) -> usize
+    where T: Display
+{
+    let labels: Vec<&T> = options.iter().map(|(option, _)| option).collect();
+
+    let config = SelectConfig {
+        hints: options.iter().map(|(_, hint)| hint.to_string()).collect(),
+        ..SelectConfig::new(options.len())
+    };
+
+    event::with_terminal(|source, out| select_internal(source, out, input_str, &labels, config, &Theme::default()))
+        .expect("options must not be empty")
+}
+
+/// Same as `select_input`, but if nothing is chosen within `timeout` the option at `default` is chosen instead.
+/// The selection starts on `default`, with the seconds left counting down dimmed after it.
+/// Like the timeout of a text input, it covers the whole selection and isn't reset by key presses.
+/// ## Example
+/// ```no_run
+/// use std::time::Duration;
+/// use painless_input::select_input_timeout;
+///
+/// let index = select_input_timeout("Boot: ", &["Linux", "Windows"], Duration::from_secs(10), 0);
+/// println!();
+/// ```
+/// ```
+/// use std::time::Duration;
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{select_input_timeout, VecEventSource};
+///
+/// // Moving the selection doesn't stop the timeout
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+/// ]);
+///
+/// let (index, output) = source.run(|| select_input_timeout("Pick: ", &["a", "b", "c"], Duration::ZERO, 0));
+/// assert_eq!(index, 0);
+/// // The default is left on the line as the choice
+/// assert!(output.ends_with("[a]⭥\x1b[0m\x1b[?25h\x1b[0m\x1b[?25h"));
+/// ```
+/// ## Panics
+/// Panics if `default` isn't the index of one of the options, which it can't be if `options` is empty.
+pub fn select_input_timeout<T>(input_str: &str, options: &[T], timeout: Duration, default: usize) -> usize
+    where T: Display
+{
+    assert!(default < options.len(), "default must be the index of an option");
+
+    let config = SelectConfig {
+        timeout: Some((timeout, default)),
+        ..SelectConfig::new(options.len())
+    };
+
+    event::with_terminal(|source, out| select_internal(source, out, input_str, options, config, &Theme::default()))
+        .expect("options must not be empty")
+}
+
+/// Same as `select_input`, but returns a clone of the chosen option instead of its index.
+/// Returns `Error::NoOptions` if `options` is empty.
+/// ## Example
+/// ```no_run
+/// use painless_input::select_value;
+///
+/// let colour = select_value("Choose a colour: ", &["Red", "Green", "Blue"]).unwrap();
+/// println!();
+/// println!("You chose {}", colour);
+/// ```
+pub fn select_value<T>(input_str: &str, options: &[T]) -> Result<T, Error>
+    where T: Display + Clone
+{
+    let index = select_input(input_str, options)?;
+
+    Ok(options[index].clone())
+}
+
+/// Everything about a select other than its prompt and options
+struct SelectConfig {
+    /// Whether moving past either end of the list goes round to the other end
+    wrap: bool,
+    /// Whether the user can press Esc to back out of the selection
+    cancelable: bool,
+    /// Whether each option is skipped over and can't be chosen
+    disabled: Vec<bool>,
+    /// A description of each option shown under the prompt, or empty for no descriptions
+    hints: Vec<String>,
+    /// How long the user has to choose, and the index of the option chosen for them once that runs out
+    timeout: Option<(Duration, usize)>,
+}
+
+impl SelectConfig {
+    fn new(option_count: usize) -> Self {
+        Self {
+            wrap: false,
+            cancelable: false,
+            disabled: vec![false; option_count],
+            hints: Vec::new(),
+            timeout: None,
+        }
+    }
+}
+
+/// Returns `Error::NoOptions` if `options` is empty, or `Error::Cancelled` if `config.cancelable` is set and the user pressed Esc
+fn select_internal<T>(
+    source: &mut dyn EventSource,
+    out: &mut Output,
+    input_str: &str,
+    options: &[T],
+    config: SelectConfig,
+    theme: &Theme,
+) -> Result<usize, Error>
+    where T: Display
+{
+    if options.is_empty() {
+        return Err(Error::NoOptions);
+    }
+
+    require_interactive(source, "select_input");
+
+    let SelectConfig { wrap, cancelable, disabled, hints, timeout } = config;
+
+    let footer = theme.footer(if cancelable { "↑/↓ choose · enter confirm · esc cancel" } else { "↑/↓ choose · enter confirm" }, out.width());
+
+    // The timeout is for the whole selection, it isn't reset by key presses
+    let deadline = timeout.map(|(timeout, _)| Instant::now() + timeout);
+
+    // The countdown is drawn after the arrow as a space and whole seconds left, it only gets shorter so the first one is the widest
+    let countdown_width = timeout.map_or(0, |(timeout, _)| format!(" {}s", timeout.as_secs_f64().ceil() as u64).width() as u16);
+
+    // Seconds left that are on screen, if any
+    let mut shown_secs = None;
+
+    // Number of rows drawn below the prompt line, which are the hint and the footer if there are any
+    let mut drawn_rows = 0;
+
+    // The hint of the option at `index` and the footer, whichever of them there are
+    let rows_below = |index: usize| -> Vec<String> {
+        hints.get(index).map(|hint| theme.hint(hint).to_string()).into_iter().chain(footer.clone()).collect()
+    };
+
+    // The next enabled option above or below `cursor`, going round the end of the list if wrapping
+    let next_enabled = |cursor: usize, up: bool| {
+        let len = options.len();
+
+        (1..len)
+            .map(|offset| if up { (cursor + len - offset) % len } else { (cursor + offset) % len })
+            .filter(|i| wrap || if up { *i < cursor } else { *i > cursor })
+            .find(|i| !disabled[*i])
+    };
+
+    // Hide cursor
+    crossterm::queue!(out, crossterm::cursor::Hide).unwrap();
+
+    // Start on the option that would be chosen when the time runs out, or the first option that can be chosen
+    let mut cursor: usize = match timeout {
+        Some((_, default)) => default,
+        None => disabled.iter().position(|disabled| !disabled).expect("select_input_with_disabled checks that an option is enabled"),
+    };
+    let mut longest_option = 0;
+
+    // Labels are cut short to fit on the line after the prompt, the brackets and the arrow
+    let label_width = (out.width() as usize).saturating_sub(display_width(input_str) as usize + display_width(&theme.up_down_arrow) as usize + 3);
+    let labels: Vec<String> = options.iter().map(|option| truncate_to_width(&option.to_string(), label_width)).collect();
+
+    for label in &labels {
+        let option_len = label.width();
+        if option_len > longest_option {
+            longest_option = option_len;
+        }
+    }
+
+    crossterm::queue!(out, crossterm::style::Print(input_str), crossterm::style::PrintStyledContent(theme.value(format!("[{}]{}", labels[cursor], theme.up_down_arrow)))).unwrap();
+
+    // Columns taken up by the prompt line with the option at `index`, to get back to the end of it after drawing the hint
+    let line_width = |index: usize| (display_width(input_str) as usize + labels[index].width() + display_width(&theme.up_down_arrow) as usize + 2) as u16;
+
+    let rows = rows_below(cursor);
+
+    if !rows.is_empty() {
+        draw_rows(out, &rows, &mut drawn_rows);
+        crossterm::queue!(out, crossterm::cursor::MoveToColumn(line_width(cursor))).unwrap();
+    }
+
+    loop {
+        let mut to_update = false;
+
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let secs = remaining.as_secs_f64().ceil() as u64;
+
+            // Redraw the countdown each time a second goes by
+            if secs > 0 && shown_secs != Some(secs) {
+                crossterm::queue!(out, crossterm::style::PrintStyledContent(theme.hint(format!(" {}s", secs))), crossterm::cursor::MoveToColumn(line_width(cursor))).unwrap();
+                shown_secs = Some(secs);
+            }
+
+            // Everything drawn so far has to be shown before waiting
+            out.flush().unwrap();
+
+            // Wait until either a key is pressed or the countdown goes down
+            let until_tick = remaining.saturating_sub(Duration::from_secs(secs.saturating_sub(1)));
+
+            if !source.poll(until_tick).unwrap() {
+                if !remaining.is_zero() {
+                    continue;
+                }
+
+                // Out of time, so show the default as the choice and finish as if it was chosen
+                crossterm::queue!(out, crossterm::style::Print("\r"), crossterm::style::Print(input_str)).unwrap();
+                clear_right(out, longest_option as u16 + display_width(&theme.up_down_arrow) + 2 + countdown_width);
+
+                cursor = timeout.unwrap().1;
+
+                crossterm::queue!(out, crossterm::style::PrintStyledContent(theme.value(format!("[{}]{}", labels[cursor], theme.up_down_arrow)))).unwrap();
+                break;
+            }
+        }
+
+        let key_event = read_event(source, out);
+
+        if let crossterm::event::Event::Key(key) = key_event {
+            if key.kind != crossterm::event::KeyEventKind::Press {
+                continue;
+            }
+
+            // Ctrl+C cancels the prompt if it can be cancelled, otherwise it exits the program
+            let code = if is_interrupt(&key) {
+                if !cancelable {
+                    interrupt(out);
+                }
+
+                crossterm::event::KeyCode::Esc
+            } else {
+                key.code
+            };
+
+            match code {
+                crossterm::event::KeyCode::Enter => {
+                    // Clear the countdown
+                    if shown_secs.is_some() {
+                        clear_right(out, countdown_width);
+                    }
+
+                    break;
+                }
+                crossterm::event::KeyCode::Esc if cancelable => {
+                    // Clear the option from the line, leaving just the prompt
+                    crossterm::queue!(out, crossterm::style::Print("\r"), crossterm::style::Print(input_str)).unwrap();
+
+                    // +2 for the [ and ]
+                    clear_right(out, longest_option as u16 + display_width(&theme.up_down_arrow) + 2 + countdown_width);
+
+                    // Clear the hint and the footer
+                    if drawn_rows > 0 {
+                        draw_rows(out, &[], &mut drawn_rows);
+                        crossterm::queue!(out, crossterm::cursor::MoveToColumn(display_width(input_str))).unwrap();
+                    }
+
+                    // Show cursor
+                    crossterm::queue!(out, crossterm::cursor::Show).unwrap();
+
+                    return Err(Error::Cancelled);
+                }
+                crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Char('k') => {
+                    if let Some(above) = next_enabled(cursor, true) {
+                        cursor = above;
+                    }
+
+                    to_update = true;
+                }
+                crossterm::event::KeyCode::Down | crossterm::event::KeyCode::Char('j') => {
+                    if let Some(below) = next_enabled(cursor, false) {
+                        cursor = below;
+                    }
+
+                    to_update = true;
+                }
+                // 1 to 9 jump straight to that option, digits past the end of the list or on disabled options are ignored
+                crossterm::event::KeyCode::Char(digit @ '1'..='9') => {
+                    let index = digit as usize - '1' as usize;
+
+                    if index < options.len() && !disabled[index] {
+                        cursor = index;
+                        to_update = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if to_update {
+            // Clear line
+            crossterm::queue!(out, crossterm::style::Print("\r")).unwrap();
+
+            // Print input_str
+            crossterm::queue!(out, crossterm::style::Print(input_str)).unwrap();
+
+            // Clear enough to get rid of everything on the right, including the countdown
+            // +2 for the [ and ]
+            clear_right(out, longest_option as u16 + display_width(&theme.up_down_arrow) + 2 + countdown_width);
+
+            // Print the option
+            crossterm::queue!(out, crossterm::style::PrintStyledContent(theme.value(format!("[{}]{}", labels[cursor], theme.up_down_arrow)))).unwrap();
+
+            // Print the hint of the new option under the prompt
+            let rows = rows_below(cursor);
+
+            if !rows.is_empty() {
+                draw_rows(out, &rows, &mut drawn_rows);
+                crossterm::queue!(out, crossterm::cursor::MoveToColumn(line_width(cursor))).unwrap();
+            }
+
+            // The countdown was cleared along with the old option
+            shown_secs = None;
+        }
+    }
+
+    // Clear the hint and the footer, leaving the cursor after the chosen option
+    if drawn_rows > 0 {
+        draw_rows(out, &[], &mut drawn_rows);
+        crossterm::queue!(out, crossterm::cursor::MoveToColumn(line_width(cursor))).unwrap();
+    }
+
+    // Show cursor
+    crossterm::queue!(out, crossterm::cursor::Show).unwrap();
+
+    Ok(cursor)
+}
+
+/// Select an input from the user, typing to filter the options.
+/// The options that fuzzy match the typed text are listed below the prompt, best match first, with the matched characters in bold.
+/// An option matches if it has all of the typed characters in order, ignoring case, so `opt3` matches `Option 3`. See [`fuzzy_match`].
+/// Use the up and down arrows to navigate, backspace to edit the filter and enter to submit.
+/// Returns the index of the chosen option in `options`.
+/// ## Example
+/// ```no_run
+/// use painless_input::select_filter;
+///
+/// let options = ["Apple", "Banana", "Cherry", "Grape", "Orange"];
+/// let selected = select_filter("Choose a fruit: ", &options);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{select_filter, VecEventSource};
+///
+/// let options = ["Option 1", "Option 2", "Option 3", "Other"];
+///
+/// let mut events: Vec<Event> = "opt3".chars().map(|c| Event::Key(KeyEvent::from(KeyCode::Char(c)))).collect();
+/// events.push(Event::Key(KeyEvent::from(KeyCode::Enter)));
+///
+/// let (selected, _) = VecEventSource::new(events).run(|| select_filter("Choose: ", &options));
+/// assert_eq!(selected, 2);
+///
+/// // Pasting filters the same as typing
+/// let source = VecEventSource::new(vec![
+///     Event::Paste(String::from("oth")),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (selected, output) = source.run(|| select_filter("Choose: ", &options));
+/// assert_eq!(selected, 3);
+/// assert!(output.contains("Choose: oth"));
+/// ```
+/// ## Panics
+/// Panics if `options` is empty.
+pub fn select_filter<T>(input_str: &str, options: &[T]) -> usize
+    where T: Display
+{
+    select_filter_with_theme(input_str, options, &Theme::default())
+}
+
+/// Same as `select_filter`, but drawn using `theme`.
+/// Panics if `options` is empty.
+pub fn select_filter_with_theme<T>(input_str: &str, options: &[T], theme: &Theme) -> usize
+    where T: Display
+{
+    event::with_terminal(|source, out| select_filter_internal(source, out, input_str, options, theme))
+        .expect("options must not be empty")
+}
+
+/// Returns `Error::NoOptions` if `options` is empty
+fn select_filter_internal<T>(
+    source: &mut dyn EventSource,
+    out: &mut Output,
+    input_str: &str,
+    options: &[T],
+    theme: &Theme,
+) -> Result<usize, Error>
+    where T: Display
+{
+    if options.is_empty() {
+        return Err(Error::NoOptions);
+    }
+
+    require_interactive(source, "select_filter");
+
+    let labels: Vec<String> = options.iter().map(|option| option.to_string()).collect();
+
+    let mut filter = String::new();
+
+    // Indices of the options that match the filter, best match first
+    let mut matches: Vec<usize> = (0..options.len()).collect();
+
+    // Which chars of each of the matches matched the filter, in the same order as matches
+    let mut matched_chars: Vec<Vec<usize>> = vec![Vec::new(); options.len()];
+
+    // Position of the highlighted option in matches
+    let mut cursor: usize = 0;
+
+    // Number of rows drawn below the prompt line
+    let mut drawn_rows = 0;
+
+    let mut first_iter = true;
+
+    loop {
+        let mut update = false;
+        let mut filter_changed = false;
+
+        // If on the first iter, just print and don't wait for input
+        if first_iter {
+            first_iter = false;
+            update = true;
+        } else {
+            let key_event = read_event(source, out);
+
+            match key_event {
+                crossterm::event::Event::Key(key) if key.kind == crossterm::event::KeyEventKind::Press => {
+                    if is_interrupt(&key) {
+                        interrupt(out);
+                    }
+
+                    match key.code {
+                        // Nothing to submit if nothing matches
+                        crossterm::event::KeyCode::Enter if !matches.is_empty() => {
+                            break;
+                        }
+                        crossterm::event::KeyCode::Up => {
+                            cursor = cursor.saturating_sub(1);
+
+                            update = true;
+                        }
+                        crossterm::event::KeyCode::Down => {
+                            if cursor + 1 < matches.len() {
+                                cursor += 1;
+                            }
+
+                            update = true;
+                        }
+                        crossterm::event::KeyCode::Char(c) => {
+                            filter.push(c);
+
+                            filter_changed = true;
+                        }
+                        crossterm::event::KeyCode::Backspace => {
+                            filter_changed = filter.pop().is_some();
+                        }
+                        _ => {}
+                    }
+                }
+                crossterm::event::Event::Paste(text) => {
+                    filter.extend(pasted_chars(&text));
+
+                    filter_changed = true;
+                }
+                _ => {}
+            }
+        }
+
+        if filter_changed {
+            let mut scored: Vec<(usize, FuzzyMatch)> = (0..labels.len())
+                .filter_map(|i| fuzzy_match(&filter, &labels[i]).map(|found| (i, found)))
+                .collect();
+
+            // The sort is stable, so options that match as well as each other stay in their order
+            scored.sort_by_key(|(_, found)| std::cmp::Reverse(found.score));
+
+            matches = scored.iter().map(|(i, _)| *i).collect();
+            matched_chars = scored.into_iter().map(|(_, found)| found.positions).collect();
+
+            // Go back to the first match
+            cursor = 0;
+            update = true;
+        }
+
+        if update {
+            // Leave room for the highlight marker, and a column so the terminal doesn't wrap
+            let row_width = (out.width() as usize).saturating_sub(display_width(&theme.highlight_marker) as usize + 1);
+
+            let rows: Vec<String> = if matches.is_empty() {
+                vec![theme.hint("(no matches)").to_string()]
+            } else {
+                matches
+                    .iter()
+                    .enumerate()
+                    .map(|(i, index)| {
+                        let shown = truncate_to_width(&labels[*index], row_width);
+
+                        // The … of a cut off label takes the place of a char, so it isn't one of the matches
+                        let visible = if shown != labels[*index] { shown.chars().count() - 1 } else { usize::MAX };
+                        let matched: Vec<usize> = matched_chars[i].iter().copied().filter(|position| *position < visible).collect();
+
+                        theme.row_with_matches(&shown, i == cursor, &matched)
+                    })
+                    .collect()
+            };
+
+            draw_rows(out, &rows, &mut drawn_rows);
+
+            // Redraw the prompt line, leaving the cursor after the filter
+            crossterm::queue!(out, crossterm::style::Print("\r"), crossterm::style::Print(input_str), crossterm::style::Print(&filter), crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)).unwrap();
+        }
+    }
+
+    // Clear the list and leave the chosen option on the prompt line
+    draw_rows(out, &[], &mut drawn_rows);
+
+    crossterm::queue!(out, crossterm::style::Print("\r"), crossterm::style::Print(input_str), crossterm::style::Print(&labels[matches[cursor]]), crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)).unwrap();
+
+    Ok(matches[cursor])
+}
+
+/// Select an input from the user from a vertical list that shows up to `visible_rows` options at a time.
+/// The list scrolls as the highlight moves past the top or bottom, with ▲ and ▼ showing that there are more options above or below.
+/// Use the up and down arrows (or k and j) to navigate, page up and page down to move a page at a time and enter to submit.
+/// Returns the index of the chosen option.
+/// ## Example
+/// ```no_run
+/// use painless_input::select_list;
+///
+/// let options: Vec<String> = (1..=50).map(|i| format!("Option {}", i)).collect();
+/// let selected = select_list("Choose an option: ", &options, 5);
+/// println!();
+/// ```
+/// ## Panics
+/// Panics if `options` is empty.
+pub fn select_list<T>(input_str: &str, options: &[T], visible_rows: usize) -> usize
+    where T: Display
+{
+    select_list_with_theme(input_str, options, visible_rows, &Theme::default())
+}
+
+/// Same as `select_list`, but drawn using `theme`.
+/// Panics if `options` is empty.
+pub fn select_list_with_theme<T>(input_str: &str, options: &[T], visible_rows: usize, theme: &Theme) -> usize
+    where T: Display
+{
+    event::with_terminal(|source, out| select_list_internal(source, out, input_str, options, &[], visible_rows, theme))
+        .expect("options must not be empty")
+}
+
+/// Same as `select_list`, but every option is shown at once on its own line, with the current one highlighted.
+/// Use `select_list` instead if there are more options than fit on the screen.
+///
+/// With the `mouse` feature, clicking an option in either list highlights it, and clicking it again or double clicking chooses it.
+/// ## Example
+/// ```no_run
+/// use painless_input::select_vertical;
+///
+/// let selected = select_vertical("Choose a size: ", &["Small", "Medium", "Large"]);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{select_vertical, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (index, output) = source.run(|| select_vertical("Choose a size: ", &["Small", "Medium", "Large"]));
+/// assert_eq!(index, 2);
+/// // No scroll markers, as everything fits
+/// assert!(output.contains("Small") && output.contains("Medium") && !output.contains("▼"));
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+/// use painless_input::{select_vertical, VecEventSource};
+///
+/// // The scripted terminal starts on the top row, so the options are on the rows below it
+/// let click = |row: u16| Event::Mouse(MouseEvent {
+///     kind: MouseEventKind::Down(MouseButton::Left),
+///     column: 4,
+///     row,
+///     modifiers: KeyModifiers::NONE,
+/// });
+///
+/// // Clicking past the list does nothing, then Large is highlighted and chosen with a double click
+/// let source = VecEventSource::new(vec![click(7), click(3), click(3)]);
+///
+/// let (index, _) = source.run(|| select_vertical("Choose a size: ", &["Small", "Medium", "Large"]));
+/// assert_eq!(index, 2);
+/// ```
+/// ```
+/// use painless_input::{select_vertical, VecEventSource};
+///
+/// let options: Vec<String> = Vec::new();
+///
+/// let (res, _) = VecEventSource::new(vec![]).run_catching(|| select_vertical("Choose a size: ", &options));
+/// let message = res.unwrap_err().downcast::<String>().unwrap();
+/// assert!(message.starts_with("options must not be empty"));
+/// ```
+/// ## Panics
+/// Panics if `options` is empty.
+pub fn select_vertical<T>(input_str: &str, options: &[T]) -> usize
+    where T: Display
+{
+    event::with_terminal(|source, out| select_list_internal(source, out, input_str, options, &[], options.len(), &Theme::default()))
+        .expect("options must not be empty")
+}
+
+/// Same as `select_vertical`, but the options are sorted into groups, each shown under a header with its name.
+/// Headers can't be chosen and are skipped over when navigating. Empty groups are shown with just their header.
+/// Returns the index of the group the chosen option is in, and the index of the option in that group.
+/// ## Example
+/// ```no_run
+/// use painless_input::select_grouped;
+///
+/// let groups = [
+///     (String::from("Network"), vec!["Wi-Fi", "Ethernet"]),
+///     (String::from("Storage"), vec!["Disks", "Backups"]),
+/// ];
+///
+/// let (group, index) = select_grouped("Settings: ", &groups);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{select_grouped, VecEventSource};
+///
+/// let groups = [
+///     (String::from("Network"), vec!["Wi-Fi", "Ethernet"]),
+///     (String::from("Empty"), vec![]),
+///     (String::from("Storage"), vec!["Disks", "Backups"]),
+/// ];
+///
+/// let source = VecEventSource::new(vec![
+///     // Skips over both headers in between
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (chosen, output) = source.run(|| select_grouped("Settings: ", &groups));
+/// assert_eq!(chosen, (2, 0));
+/// assert!(output.contains("--- Network ---"));
+/// assert!(output.contains("  Wi-Fi"));
+/// ```
+/// ## Panics
+/// Panics if there are no options in any of the groups.
+pub fn select_grouped<T>(input_str: &str, groups: &[(String, Vec<T>)]) -> (usize, usize)
+    where T: Display
+{
+    let mut rows = Vec::new();
+    let mut headers = Vec::new();
+
+    // The group and in-group index of each row, for turning the chosen row back into them
+    let mut positions = Vec::new();
+
+    for (group, (name, options)) in groups.iter().enumerate() {
+        rows.push(name.clone());
+        headers.push(true);
+        positions.push((group, 0));
+
+        for (index, option) in options.iter().enumerate() {
+            rows.push(option.to_string());
+            headers.push(false);
+            positions.push((group, index));
+        }
+    }
+
+    assert!(headers.contains(&false), "at least one group must have options");
+
+    let row = event::with_terminal(|source, out| select_list_internal(source, out, input_str, &rows, &headers, rows.len(), &Theme::default()))
+        .expect("there's a row for every group");
+
+    positions[row]
+}
+
+/// Rows marked in `headers` are group headers, which can't be chosen, an empty slice means there aren't any.
+/// Returns `Error::NoOptions` if `options` is empty.
+fn select_list_internal<T>(
+    source: &mut dyn EventSource,
+    out: &mut Output,
+    input_str: &str,
+    options: &[T],
+    headers: &[bool],
+    visible_rows: usize,
+    theme: &Theme,
+) -> Result<usize, Error>
+    where T: Display
+{
+    if options.is_empty() {
+        return Err(Error::NoOptions);
+    }
+
+    require_interactive(source, "select_list");
+
+    let labels: Vec<String> = options.iter().map(|option| option.to_string()).collect();
+
+    let is_header = |i: usize| headers.get(i).copied().unwrap_or(false);
+
+    // The closest row that can be chosen, looking from `from` towards the top or bottom, and then the other way if there isn't one
+    let nearest_option = |from: usize, up: bool| {
+        let towards = |up: bool| {
+            if up {
+                (0..=from).rev().find(|i| !is_header(*i))
+            } else {
+                (from..options.len()).find(|i| !is_header(*i))
+            }
+        };
+
+        towards(up).or_else(|| towards(!up)).unwrap_or(from)
+    };
+
+    // Always show at least one row
+    let visible_rows = visible_rows.max(1);
+
+    let mut cursor: usize = nearest_option(0, false);
+
+    // Index of the option at the top of the visible window
+    let mut top = 0;
+
+    // Number of rows drawn below the prompt line
+    let mut drawn_rows = 0;
+
+    // Hide cursor
+    crossterm::queue!(out, crossterm::cursor::Hide).unwrap();
+
+    // Print input_str as bold
+    crossterm::queue!(out, crossterm::style::PrintStyledContent(theme.prompt(input_str))).unwrap();
+
+    #[cfg(feature = "mouse")]
+    out.enable_mouse_capture();
+
+    // Row of the terminal the prompt is on, the list is drawn on the rows below it
+    let mut prompt_row = out.cursor_row();
+
+    let mut first_iter = true;
+
+    loop {
+        let mut update = false;
+
+        // If on the first iter, just print and don't wait for input
+        if first_iter {
+            first_iter = false;
+            update = true;
+        } else {
+            let key_event = read_event(source, out);
+
+            match key_event {
+                // Clicking an option highlights it, and clicking the highlighted option chooses it
+                crossterm::event::Event::Mouse(mouse) if mouse.kind == crossterm::event::MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
+                    let bottom = (top + visible_rows).min(options.len());
+
+                    let clicked = prompt_row
+                        .and_then(|row| (mouse.row as usize).checked_sub(row as usize + 1))
+                        .map(|offset| top + offset)
+                        .filter(|i| *i < bottom && !is_header(*i));
+
+                    match clicked {
+                        Some(i) if i == cursor => break,
+                        Some(i) => {
+                            cursor = i;
+                            update = true;
+                        }
+                        None => {}
+                    }
+                }
+                crossterm::event::Event::Key(key) if key.kind == crossterm::event::KeyEventKind::Press => {
+                    if is_interrupt(&key) {
+                        interrupt(out);
+                    }
+
+                    match key.code {
+                        crossterm::event::KeyCode::Enter => {
+                            break;
+                        }
+                        crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Char('k') => {
+                            // Only moves if there's an option above, not just a header
+                            if let Some(above) = (0..cursor).rev().find(|i| !is_header(*i)) {
+                                cursor = above;
+                            }
+
+                            update = true;
+                        }
+                        crossterm::event::KeyCode::Down | crossterm::event::KeyCode::Char('j') => {
+                            if let Some(below) = (cursor + 1..options.len()).find(|i| !is_header(*i)) {
+                                cursor = below;
+                            }
+
+                            update = true;
+                        }
+                        crossterm::event::KeyCode::PageUp => {
+                            cursor = nearest_option(cursor.saturating_sub(visible_rows), true);
+
+                            update = true;
+                        }
+                        crossterm::event::KeyCode::PageDown => {
+                            cursor = nearest_option((cursor + visible_rows).min(options.len().saturating_sub(1)), false);
+
+                            update = true;
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if update {
+            // Leave room for the scroll marker and the highlight marker, and a column so the terminal doesn't wrap
+            // Options in groups are indented by 2 under their header
+            let indent = if headers.is_empty() { "" } else { "  " };
+            let row_width = (out.width() as usize).saturating_sub(display_width(&theme.highlight_marker) as usize + 3 + indent.len());
+
+            // Scroll the window so the cursor is visible
+            if cursor < top {
+                top = cursor;
+
+                // Keep the header of the group in view when scrolling up to its first option
+                if visible_rows > 1 && top > 0 && is_header(top - 1) {
+                    top -= 1;
+                }
+            } else if cursor >= top + visible_rows {
+                top = cursor + 1 - visible_rows;
+            }
+
+            let bottom = (top + visible_rows).min(options.len());
+
+            let mut rows: Vec<String> = (top..bottom)
+                .map(|i| {
+                    let marker = if i == top && top > 0 {
+                        theme.more_above.as_str()
+                    } else if i == bottom - 1 && bottom < options.len() {
+                        theme.more_below.as_str()
+                    } else {
+                        " "
+                    };
+
+                    if is_header(i) {
+                        format!("{} {}", marker, theme.prompt(truncate_to_width(&format!("--- {} ---", labels[i]), row_width + indent.len())))
+                    } else {
+                        format!("{} {}{}", marker, indent, theme.row(&truncate_to_width(&labels[i], row_width), i == cursor))
+                    }
+                })
+                .collect();
+
+            rows.extend(theme.footer("↑/↓ navigate · pgup/pgdn page · enter confirm", out.width()));
+
+            draw_rows(out, &rows, &mut drawn_rows);
+
+            // The terminal scrolls the prompt up if the rows went past the bottom of it
+            prompt_row = prompt_row.map(|row| row.min(out.height().saturating_sub(1 + drawn_rows as u16)));
+        }
+    }
+
+    #[cfg(feature = "mouse")]
+    out.disable_mouse_capture();
+
+    // Clear the list and leave the chosen option on the prompt line
+    draw_rows(out, &[], &mut drawn_rows);
+
+    crossterm::queue!(out, crossterm::style::Print("\r"), crossterm::style::Print(input_str), crossterm::style::Print(&labels[cursor]), crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)).unwrap();
+
+    // Show cursor
+    crossterm::queue!(out, crossterm::cursor::Show).unwrap();
+
+    Ok(cursor)
+}
+
+/// Select any number of options from the user using checkboxes, the options can be anything that implements `Display`.
+/// Use the up and down arrows (or k and j) to navigate, space to toggle an option and enter to submit.
+/// Page up and page down move a screen at a time, home goes to the first option and end to the submit button.
+/// `a` selects every option, `n` deselects them all and `i` inverts the selection.
+/// Returns whether each option was selected, or `Error::NoOptions` if `options` is empty.
+///
+/// The list is drawn again from the prompt line down if the terminal is resized, so rows rewrapped by the terminal don't corrupt it.
+/// ## Example
+/// ```no_run
+/// use painless_input::multiselect_input;
+///
+/// let toppings = multiselect_input("Choose toppings:", "Done", &["Cheese", "Ham", "Pineapple"]).unwrap();
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{multiselect_input, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Resize(40, 10),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (selections, output) = source.run(|| multiselect_input("Choose:", "Done", &["A", "B"]).unwrap());
+/// assert_eq!(selections, vec![true, false]);
+///
+/// // After the resize, the prompt line is cleared downwards and the whole list is drawn again
+/// let redraw = output.split("\r\x1b[J").nth(1).unwrap();
+/// assert!(redraw.contains("Choose:"));
+/// assert!(redraw.contains("☑ A"));
+/// assert!(redraw.contains("☐ B"));
+/// ```
+/// ```
+/// use std::fmt::Display;
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{multiselect_input, VecEventSource};
+///
+/// enum Feature {
+///     Logging,
+///     Metrics,
+/// }
+///
+/// impl Display for Feature {
+///     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+///         match self {
+///             Feature::Logging => write!(f, "Logging"),
+///             Feature::Metrics => write!(f, "Metrics"),
+///         }
+///     }
+/// }
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (selections, output) = source.run(|| multiselect_input("Features:", "Save", &[Feature::Logging, Feature::Metrics]).unwrap());
+/// assert_eq!(selections, vec![false, true]);
+/// assert!(output.contains("☑ Metrics"));
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{multiselect_input, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('a'))),
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (selections, _) = source.run(|| multiselect_input("Choose:", "Done", &["A", "B", "C"]).unwrap());
+/// assert_eq!(selections, vec![true, false, true]);
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('i'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (selections, output) = source.run(|| multiselect_input("Choose:", "Done", &["A", "B", "C"]).unwrap());
+/// assert_eq!(selections, vec![false, true, true]);
+/// // Every row is redrawn with its new checkbox
+/// assert!(output.contains("☐ A") && output.contains("☑ B") && output.contains("☑ C"));
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{multiselect_input, VecEventSource};
+///
+/// // The scripted terminal is 24 rows high, so a page is 22 options
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::PageDown)),
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     // Stops on the submit button
+///     Event::Key(KeyEvent::from(KeyCode::PageDown)),
+///     Event::Key(KeyEvent::from(KeyCode::PageUp)),
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::Home)),
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::End)),
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let options: Vec<u32> = (0..30).collect();
+/// let (selections, _) = source.run(|| multiselect_input("Choose:", "Done", &options).unwrap());
+/// let selected: Vec<usize> = (0..30).filter(|i| selections[*i]).collect();
+/// assert_eq!(selected, vec![0, 8, 22]);
+/// ```
+pub fn multiselect_input<T>(input_str: &str, submit_str: &str, options: &[T]) -> Result<Vec<bool>, Error>
+    where T: Display
+{
+    event::with_terminal(|source, out| {
+        multiselect_internal(source, out, input_str, submit_str, options, MultiselectConfig::new(options.len()), &Theme::default())
+    })
+}
+
+/// Same as `multiselect_input`, but drawn using `theme`.
+/// Panics if `options` is empty.
+pub fn multiselect_input_with_theme<T>(input_str: &str, submit_str: &str, options: &[T], theme: &Theme) -> Vec<bool>
+    where T: Display
+{
+    event::with_terminal(|source, out| {
+        multiselect_internal(source, out, input_str, submit_str, options, MultiselectConfig::new(options.len()), theme)
+    })
+        .expect("options must not be empty")
+}
+
+/// Same as `multiselect_input`, but the checkboxes start out ticked according to `defaults`.
+/// Useful for editing existing settings where some options are already enabled.
+/// ## Example
+/// ```no_run
+/// use painless_input::multiselect_input_with_defaults;
+///
+/// let selected = multiselect_input_with_defaults("Enabled features: ", "Save", &["Logging", "Metrics", "Tracing"], &[true, false, true]);
+/// println!();
+/// ```
+/// ## Panics
+/// Panics if `options` is empty, or if `defaults` isn't the same length as `options`.
+pub fn multiselect_input_with_defaults<T>(
+    input_str: &str,
+    submit_str: &str,
+    options: &[T],
+    defaults: &[bool],
+) -> Vec<bool>
+    where T: Display
+{
+    assert_eq!(defaults.len(), options.len(), "defaults must have one entry per option");
+
+    let config = MultiselectConfig {
+        defaults: defaults.to_vec(),
+        ..MultiselectConfig::new(options.len())
+    };
+
+    event::with_terminal(|source, out| {
+        multiselect_internal(source, out, input_str, submit_str, options, config, &Theme::default())
+    })
+        .expect("options must not be empty")
+}
+
+/// Same as `multiselect_input`, but the user has to select between `min` and `max` options.
+/// Toggling an option on when `max` options are already selected is blocked, and submitting with fewer than `min` shows an error next to the submit button.
+/// ## Example
+/// ```no_run
+/// use painless_input::multiselect_input_constrained;
+///
+/// let selected = multiselect_input_constrained("Pick 2 or 3 toppings: ", "Done", &["Cheese", "Ham", "Olives", "Onion"], 2, 3);
+/// println!();
+/// ```
+/// ## Panics
+/// Panics if `options` is empty, or if `min` is greater than `max`.
+pub fn multiselect_input_constrained<T>(
+    input_str: &str,
+    submit_str: &str,
+    options: &[T],
+    min: usize,
+    max: usize,
+) -> Vec<bool>
+    where T: Display
+{
+    assert!(min <= max, "min selections ({}) is greater than max selections ({})", min, max);
+
+    let config = MultiselectConfig {
+        min,
+        max,
+        ..MultiselectConfig::new(options.len())
+    };
+
+    event::with_terminal(|source, out| {
+        multiselect_internal(source, out, input_str, submit_str, options, config, &Theme::default())
+    })
+        .expect("options must not be empty")
+}
+
+/// Same as `multiselect_input`, but at least one option has to be selected.
+/// Submitting with nothing selected shows an error next to the submit button, which clears on the next key press.
+/// ## Example
+/// ```no_run
+/// use painless_input::multiselect_required;
+///
+/// let selected = multiselect_required("Choose at least one: ", "Done", &["Email", "SMS", "Push"]);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{multiselect_required, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (selections, output) = source.run(|| multiselect_required("Choose:", "Done", &["A", "B"]));
+/// assert_eq!(selections, vec![false, true]);
+/// assert!(output.contains("Select at least 1"));
+/// ```
+/// ## Panics
+/// Panics if `options` is empty.
+pub fn multiselect_required<T>(input_str: &str, submit_str: &str, options: &[T]) -> Vec<bool>
+    where T: Display
+{
+    let config = MultiselectConfig {
+        min: 1,
+        ..MultiselectConfig::new(options.len())
+    };
+
+    event::with_terminal(|source, out| {
+        multiselect_internal(source, out, input_str, submit_str, options, config, &Theme::default())
+    })
+        .expect("options must not be empty")
+}
+
+/// Same as `multiselect_input`, but the options are laid out in a grid with `columns` options on each row.
+/// Up and down move within a column, and left and right (or h and l) move between the columns of a row.
+/// This fits many more options on the screen at once.
+/// ## Example
+/// ```no_run
+/// use painless_input::multiselect_columns;
+///
+/// let letters: Vec<char> = ('a'..='z').collect();
+/// let selected = multiselect_columns("Choose letters:", "Done", &letters, 4);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{multiselect_columns, VecEventSource};
+///
+/// // A | B | C
+/// // D | E
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Right)),
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::Right)),
+///     Event::Key(KeyEvent::from(KeyCode::Up)),
+///     Event::Key(KeyEvent::from(KeyCode::Right)),
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (selections, output) = source.run(|| multiselect_columns("Choose:", "Done", &["A", "B", "C", "D", "E"], 3));
+/// // Right can't go past E, so the second toggle is on C
+/// assert_eq!(selections, vec![false, false, true, false, true]);
+/// assert!(output.contains("☐ A") && output.contains("☐ D"));
+/// ```
+/// ## Panics
+/// Panics if `options` is empty, or if `columns` is 0.
+pub fn multiselect_columns<T>(input_str: &str, submit_str: &str, options: &[T], columns: usize) -> Vec<bool>
+    where T: Display
+{
+    assert!(columns > 0, "multiselect needs at least one column");
+
+    let config = MultiselectConfig {
+        columns,
+        ..MultiselectConfig::new(options.len())
+    };
+
+    event::with_terminal(|source, out| {
+        multiselect_internal(source, out, input_str, submit_str, options, config, &Theme::default())
+    })
+        .expect("options must not be empty")
+}
+
+/// Same as `multiselect_input`, but without a submit button at the bottom of the list.
+/// Enter submits from any option and Space toggles, and moving past the top or bottom of a column wraps around to the other end.
+/// ## Example
+/// ```no_run
+/// use painless_input::multiselect_without_submit;
+///
+/// let selected = multiselect_without_submit("Choose toppings:", &["Cheese", "Ham", "Pineapple"]);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{multiselect_without_submit, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     // Wraps around to A
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (selections, output) = source.run(|| multiselect_without_submit("Choose:", &["A", "B", "C"]));
+/// assert_eq!(selections, vec![true, true, false]);
+/// assert!(!output.contains("✓"));
+/// ```
+/// ## Panics
+/// Panics if `options` is empty.
+pub fn multiselect_without_submit<T>(input_str: &str, options: &[T]) -> Vec<bool>
+    where T: Display
+{
+    let config = MultiselectConfig {
+        submit_button: false,
+        ..MultiselectConfig::new(options.len())
+    };
+
+    event::with_terminal(|source, out| {
+        multiselect_internal(source, out, input_str, "", options, config, &Theme::default())
+    })
+        .expect("options must not be empty")
+}
+
+/// Same as `multiselect_input`, but typing filters the list down to the options that fuzzy match what's typed, best match first, like `select_filter`.
+/// Space toggles the option the cursor is on, so it can't be part of the filter, and Esc clears the filter to show every option again.
+/// Options keep their checkboxes while they're filtered out, with how many are selected in total shown under the list.
+/// Enter submits. Returns whether each option was selected, in the same order as `options`.
+/// ## Example
+/// ```no_run
+/// use painless_input::multiselect_filter;
+///
+/// let crates = ["serde", "serde_json", "tokio", "clap", "anyhow", "thiserror"];
+/// let selected = multiselect_filter("Dependencies: ", &crates);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{multiselect_filter, VecEventSource};
+///
+/// let typed = |text: &str| text.chars().map(|c| Event::Key(KeyEvent::from(KeyCode::Char(c)))).collect::<Vec<_>>();
+/// let key = |code: KeyCode| vec![Event::Key(KeyEvent::from(code))];
+///
+/// let events = [
+///     // The best match for "json" is serde_json
+///     typed("json"), key(KeyCode::Char(' ')),
+///     // Clearing the filter keeps serde_json selected, then tokio is picked from the whole list
+///     key(KeyCode::Esc), key(KeyCode::Down), key(KeyCode::Down), key(KeyCode::Char(' ')),
+///     key(KeyCode::Enter),
+/// ].concat();
+///
+/// let (selected, output) = VecEventSource::new(events).run(|| multiselect_filter("Crates: ", &["serde", "serde_json", "tokio"]));
+/// assert_eq!(selected, vec![false, true, true]);
+/// assert!(output.contains("1 selected"));
+/// // The chosen options are left on the prompt line
+/// assert!(output.contains("Crates: serde_json, tokio"));
+///
+/// // A pasted search term filters the same as a typed one
+/// let events = [vec![Event::Paste(String::from("tok"))], key(KeyCode::Char(' ')), key(KeyCode::Enter)].concat();
+///
+/// let (selected, _) = VecEventSource::new(events).run(|| multiselect_filter("Crates: ", &["serde", "serde_json", "tokio"]));
+/// assert_eq!(selected, vec![false, false, true]);
+/// ```
+/// ## Panics
+/// Panics if `options` is empty.
+pub fn multiselect_filter<T>(input_str: &str, options: &[T]) -> Vec<bool>
+    where T: Display
+{
+    event::with_terminal(|source, out| multiselect_filter_internal(source, out, input_str, options, &Theme::default()))
+        .expect("options must not be empty")
+}
+
+/// Same as `multiselect_input`, but returns clones of the selected options, in the same order as `options`.
+/// Returns `Error::NoOptions` if `options` is empty.
+/// ## Example
+/// ```no_run
+/// use painless_input::multiselect_values;
+///
+/// let toppings = multiselect_values("Choose toppings:", "Done", &["Cheese", "Ham", "Pineapple"]).unwrap();
+/// println!();
+/// println!("You chose {:?}", toppings);
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{multiselect_indices, multiselect_values, Error, VecEventSource};
+///
+/// let events = vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ];
+///
+/// let (values, _) = VecEventSource::new(events.clone()).run(|| multiselect_values("Choose:", "Done", &["A", "B", "C"]).unwrap());
+/// assert_eq!(values, vec!["A", "C"]);
+///
+/// let (indices, _) = VecEventSource::new(events).run(|| multiselect_indices("Choose:", "Done", &["A", "B", "C"]).unwrap());
+/// assert_eq!(indices, vec![0, 2]);
+///
+/// let options: Vec<&str> = Vec::new();
+/// let (res, _) = VecEventSource::new(vec![]).run(|| multiselect_values("Choose:", "Done", &options));
+/// assert!(matches!(res, Err(Error::NoOptions)));
+/// ```
+pub fn multiselect_values<T>(input_str: &str, submit_str: &str, options: &[T]) -> Result<Vec<T>, Error>
+    where T: Display + Clone
+{
+    let indices = multiselect_indices(input_str, submit_str, options)?;
+
+    Ok(indices.into_iter().map(|index| options[index].clone()).collect())
+}
+
+/// Same as `multiselect_input`, but returns the indices of the selected options in ascending order.
+/// Returns `Error::NoOptions` if `options` is empty.
+pub fn multiselect_indices<T>(input_str: &str, submit_str: &str, options: &[T]) -> Result<Vec<usize>, Error>
+    where T: Display
+{
+    let selections = multiselect_input(input_str, submit_str, options)?;
+
+    Ok(selections
+        .into_iter()
+        .enumerate()
+        .filter(|(_, selected)| *selected)
+        .map(|(index, _)| index)
+        .collect())
+}
+
+/// Everything about a multiselect other than its prompt and options
+struct MultiselectConfig {
+    /// Whether each option starts out selected
+    defaults: Vec<bool>,
+    /// The fewest selections that can be submitted
+    min: usize,
+    /// The most options that can be selected at once
+    max: usize,
+    /// How many options are shown side by side on each row
+    columns: usize,
+    /// Whether there's a submit button after the options for the cursor to stop on
+    submit_button: bool,
+}
+
+impl MultiselectConfig {
+    fn new(option_count: usize) -> Self {
+        Self {
+            defaults: vec![false; option_count],
+            min: 0,
+            max: option_count,
+            columns: 1,
+            submit_button: true,
+        }
+    }
+}
+
+fn multiselect_internal<T>(
+    source: &mut dyn EventSource,
+    out: &mut Output,
+    input_str: &str,
+    submit_str: &str,
+    options: &[T],
+    config: MultiselectConfig,
+    theme: &Theme,
+) -> Result<Vec<bool>, Error>
+    where T: Display
+{
+    if options.is_empty() {
+        return Err(Error::NoOptions);
+    }
+
+    require_interactive(source, "multiselect_input");
+
+    let MultiselectConfig { defaults, min, max, columns, submit_button } = config;
+
+    // The furthest the cursor can go, which is the submit button if there is one
+    let last_stop = if submit_button { options.len() } else { options.len().saturating_sub(1) };
+
+    let labels: Vec<String> = options.iter().map(|option| option.to_string()).collect();
+
+    let mut cursor = 0;
+
+    // A message shown to the right of a line, as (line index, message)
+    // The submit button is the line at options.len()
+    let mut message: Option<(usize, String)> = None;
+
+    let mut selections = defaults;
+
+    // Number of rows drawn below the prompt line, the cursor is kept on the prompt line between draws
+    let mut drawn_rows = 0;
+
+    // Hide cursor
+    crossterm::queue!(out, crossterm::cursor::Hide).unwrap();
+
+    // Print input_str as bold
+    crossterm::queue!(out, crossterm::style::PrintStyledContent(theme.prompt(input_str.trim()))).unwrap();
+
+    let mut first_iter = true;
+
+    loop {
+        let mut update = false;
+
+        // If on the first iter, just print and don't wait for input
+        if first_iter {
+            first_iter = false;
+            update = true;
+        } else {
+            let key_event = read_event(source, out);
+
+            match key_event {
+                crossterm::event::Event::Key(key) if key.kind == crossterm::event::KeyEventKind::Press => {
+                    // Any key press clears the message
+                    if message.take().is_some() {
+                        update = true;
+                    }
+
+                    let selected_count = selections.iter().filter(|selected| **selected).count();
+
+                    if is_interrupt(&key) {
+                        interrupt(out);
+                    }
+
+                    match key.code {
+                        // Enter submits from anywhere in the list, the submit button is there to show how to finish
+                        crossterm::event::KeyCode::Enter => {
+                            if selected_count < min {
+                                // Shown next to the submit button, or the option the cursor is on without one
+                                let line = if submit_button { options.len() } else { cursor };
+
+                                message = Some((line, format!("Select at least {}", min)));
+
+                                update = true;
+                            } else {
+                                break;
+                            }
+                        },
+                        // Nothing to toggle on the submit button
+                        crossterm::event::KeyCode::Char(' ') if cursor < options.len() => {
+                            // If selecting another option would go over the max
+                            if !selections[cursor] && selected_count >= max {
+                                message = Some((cursor, format!("Select at most {}", max)));
+                            } else {
+                                selections[cursor] = !selections[cursor];
+                            }
+
+                            update = true;
+                        },
+                        crossterm::event::KeyCode::Char('a') => {
+                            if options.len() > max {
+                                message = Some((cursor, format!("Select at most {}", max)));
+                            } else {
+                                selections.iter_mut().for_each(|selected| *selected = true);
+                            }
+
+                            update = true;
+                        },
+                        crossterm::event::KeyCode::Char('n') => {
+                            selections.iter_mut().for_each(|selected| *selected = false);
+
+                            update = true;
+                        },
+                        crossterm::event::KeyCode::Char('i') => {
+                            // The options that aren't selected now are the ones that will be
+                            let inverted = selections.iter().filter(|selected| !**selected).count();
+
+                            if inverted > max {
+                                message = Some((cursor, format!("Select at most {}", max)));
+                            } else {
+                                selections.iter_mut().for_each(|selected| *selected = !*selected);
+                            }
+
+                            update = true;
+                        },
+                        crossterm::event::KeyCode::Down | crossterm::event::KeyCode::Char('j') => {
+                            // If at the submit button, move to the first option
+                            // Going down from the bottom option of a column moves to the submit button
+                            // Without a submit button, it wraps around to the top of the column instead
+                            if cursor == options.len() {
+                                cursor = 0;
+                            } else if cursor + columns < options.len() {
+                                cursor += columns;
+                            } else if submit_button {
+                                cursor = options.len();
+                            } else {
+                                cursor %= columns;
+                            }
+
+                            update = true;
+                        },
+                        crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Char('k') => {
+                            // If on the top row, move to the submit button, and from the submit button to the last option
+                            // Without a submit button, it wraps around to the bottom of the column instead
+                            if cursor == options.len() {
+                                cursor = options.len().saturating_sub(1);
+                            } else if cursor >= columns {
+                                cursor -= columns;
+                            } else if submit_button {
+                                cursor = options.len();
+                            } else if !options.is_empty() {
+                                let bottom = (options.len() - 1) / columns * columns + cursor;
+
+                                // The last row can be short, in which case it's the row above it
+                                cursor = if bottom < options.len() { bottom } else { bottom - columns };
+                            }
+
+                            update = true;
+                        },
+                        // A page is as many rows as fit on the screen under the prompt, leaving room for the submit button
+                        crossterm::event::KeyCode::PageDown => {
+                            let page = (out.height() as usize).saturating_sub(2).max(1) * columns;
+
+                            // Going past the last option stops on the submit button
+                            cursor = (cursor + page).min(last_stop);
+
+                            update = true;
+                        },
+                        crossterm::event::KeyCode::PageUp => {
+                            let page = (out.height() as usize).saturating_sub(2).max(1) * columns;
+
+                            // The submit button counts as the row after the last option, going past the top stops in the same column of the top row
+                            cursor = if cursor == options.len() {
+                                options.len().saturating_sub(page)
+                            } else if cursor >= page {
+                                cursor - page
+                            } else {
+                                cursor % columns
+                            };
+
+                            update = true;
+                        },
+                        crossterm::event::KeyCode::Home => {
+                            cursor = 0;
+
+                            update = true;
+                        },
+                        crossterm::event::KeyCode::End => {
+                            cursor = last_stop;
+
+                            update = true;
+                        },
+                        // Left and right move between the columns of a row, stopping at the ends
+                        crossterm::event::KeyCode::Left | crossterm::event::KeyCode::Char('h') if cursor < options.len() && cursor % columns > 0 => {
+                            cursor -= 1;
+
+                            update = true;
+                        },
+                        crossterm::event::KeyCode::Right | crossterm::event::KeyCode::Char('l') if cursor % columns + 1 < columns && cursor + 1 < options.len() => {
+                            cursor += 1;
+
+                            update = true;
+                        },
+                        _ => {}
+                    }
+                }
+                crossterm::event::Event::Resize(_, _) => {
+                    // The terminal may have rewrapped the rows, so clear everything from the prompt line down and draw it all again
+                    crossterm::queue!(
+                        out,
+                        crossterm::style::Print("\r"),
+                        crossterm::terminal::Clear(crossterm::terminal::ClearType::FromCursorDown),
+                        crossterm::style::PrintStyledContent(theme.prompt(input_str.trim()))
+                    )
+                        .unwrap();
+
+                    drawn_rows = 0;
+                    update = true;
+                }
+                _ => {}
+            }
+        }
+
+        if update {
+            let mut rows: Vec<String> = Vec::new();
+
+            // Each column gets an equal share of the width
+            // Options are cut short to fit after the markers, the width is read on every draw so it's right after a resize
+            let column_width = out.width() as usize / columns;
+            let label_width = column_width.saturating_sub(display_width(&theme.highlight_marker) as usize + display_width(&theme.selected_marker) as usize + 2);
+
+            for row_start in (0..options.len()).step_by(columns) {
+                let row_end = (row_start + columns).min(options.len());
+                let mut row = String::new();
+
+                for i in row_start..row_end {
+                    let marker = if selections[i] { &theme.selected_marker } else { &theme.unselected_marker };
+                    let line = format!("{} {}", marker, truncate_to_width(&labels[i], label_width));
+
+                    // Underlined if cursor is on it
+                    row.push_str(&theme.row(&line, i == cursor));
+
+                    // Pad out to the next column, the styling of the row doesn't take up any room
+                    if i + 1 < row_end {
+                        row.push_str(&" ".repeat(column_width.saturating_sub(display_width(&theme.highlight_marker) as usize + line.width())));
+                    }
+                }
+
+                // A message for any option on the row goes after the whole row
+                for i in row_start..row_end {
+                    row.push_str(&line_message(theme, &message, i));
+                }
+
+                rows.push(row);
+            }
+
+            // Submit button as bold, and underlined if cursor is on it
+            if submit_button {
+                let button = theme.value(format!("{} {}", theme.submit_tick, submit_str)).to_string();
+
+                rows.push(theme.row(&button, cursor == options.len()) + &line_message(theme, &message, options.len()));
+            }
+
+            rows.extend(theme.footer("↑/↓ navigate · space toggle · a all · n none · enter confirm", out.width()));
+
+            draw_rows(out, &rows, &mut drawn_rows);
+        }
+    }
+
+    // Leave the cursor on the last row, below the rest of the list
+    crossterm::queue!(out, crossterm::cursor::MoveDown(drawn_rows as u16), crossterm::style::Print("\r")).unwrap();
+
+    // The footer is the last row, it's cleared and the cursor left on the row above it
+    if theme.help_footer && drawn_rows > 0 {
+        crossterm::queue!(out, crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine), crossterm::cursor::MoveUp(1)).unwrap();
+    }
+
+    // Show cursor
+    crossterm::queue!(out, crossterm::cursor::Show).unwrap();
+
+    Ok(selections)
+}
+
+fn multiselect_filter_internal<T>(
+    source: &mut dyn EventSource,
+    out: &mut Output,
+    input_str: &str,
+    options: &[T],
+    theme: &Theme,
+) -> Result<Vec<bool>, Error>
+    where T: Display
+{
+    if options.is_empty() {
+        return Err(Error::NoOptions);
+    }
+
+    require_interactive(source, "multiselect_filter");
+
+    let labels: Vec<String> = options.iter().map(|option| option.to_string()).collect();
+
+    // Kept for every option, whether or not it matches the filter
+    let mut selections = vec![false; options.len()];
+
+    let mut filter = String::new();
+
+    // Indices of the options that match the filter, best match first
+    let mut matches: Vec<usize> = (0..options.len()).collect();
+
+    // Which chars of each of the matches matched the filter, in the same order as matches
+    let mut matched_chars: Vec<Vec<usize>> = vec![Vec::new(); options.len()];
+
+    // Position of the highlighted option in matches
+    let mut cursor: usize = 0;
+
+    // Number of rows drawn below the prompt line
+    let mut drawn_rows = 0;
+
+    let mut first_iter = true;
+
+    loop {
+        let mut update = false;
+        let mut filter_changed = false;
+
+        // If on the first iter, just print and don't wait for input
+        if first_iter {
+            first_iter = false;
+            update = true;
+        } else {
+            let key_event = read_event(source, out);
+
+            match key_event {
+                crossterm::event::Event::Key(key) if key.kind == crossterm::event::KeyEventKind::Press => {
+                    if is_interrupt(&key) {
+                        interrupt(out);
+                    }
+
+                    match key.code {
+                        crossterm::event::KeyCode::Enter => {
+                            break;
+                        }
+                        // Toggles the option at its index in `options`, not its place in the filtered list
+                        crossterm::event::KeyCode::Char(' ') => {
+                            if let Some(index) = matches.get(cursor) {
+                                selections[*index] = !selections[*index];
+                            }
+
+                            update = true;
+                        }
+                        crossterm::event::KeyCode::Up => {
+                            cursor = cursor.saturating_sub(1);
+
+                            update = true;
+                        }
+                        crossterm::event::KeyCode::Down => {
+                            if cursor + 1 < matches.len() {
+                                cursor += 1;
+                            }
+
+                            update = true;
+                        }
+                        crossterm::event::KeyCode::Esc => {
+                            filter_changed = !filter.is_empty();
+                            filter.clear();
+                        }
+                        crossterm::event::KeyCode::Char(c) => {
+                            filter.push(c);
+
+                            filter_changed = true;
+                        }
+                        crossterm::event::KeyCode::Backspace => {
+                            filter_changed = filter.pop().is_some();
+                        }
+                        _ => {}
+                    }
+                }
+                crossterm::event::Event::Paste(text) => {
+                    // Spaces toggle rather than being typed, so they're left out of the filter
+                    filter.extend(pasted_chars(&text).filter(|c| *c != ' '));
+
+                    filter_changed = true;
+                }
+                _ => {}
+            }
+        }
+
+        if filter_changed {
+            let mut scored: Vec<(usize, FuzzyMatch)> = (0..labels.len())
+                .filter_map(|i| fuzzy_match(&filter, &labels[i]).map(|found| (i, found)))
+                .collect();
+
+            // The sort is stable, so options that match as well as each other stay in their order
+            scored.sort_by_key(|(_, found)| std::cmp::Reverse(found.score));
+
+            matches = scored.iter().map(|(i, _)| *i).collect();
+            matched_chars = scored.into_iter().map(|(_, found)| found.positions).collect();
+
+            // Go back to the first match
+            cursor = 0;
+            update = true;
+        }
+
+        if update {
+            // Leave room for the highlight marker and the checkbox, and a column so the terminal doesn't wrap
+            let row_width = (out.width() as usize).saturating_sub(display_width(&theme.highlight_marker) as usize + display_width(&theme.selected_marker) as usize + 2);
+
+            let mut rows: Vec<String> = if matches.is_empty() {
+                vec![theme.hint("(no matches)").to_string()]
+            } else {
+                matches
+                    .iter()
+                    .enumerate()
+                    .map(|(i, index)| {
+                        let marker = if selections[*index] { &theme.selected_marker } else { &theme.unselected_marker };
+                        let shown = truncate_to_width(&labels[*index], row_width);
+
+                        // The … of a cut off label takes the place of a char, so it isn't one of the matches
+                        // The positions are moved along past the checkbox and the space after it
+                        let visible = if shown != labels[*index] { shown.chars().count() - 1 } else { usize::MAX };
+                        let offset = marker.chars().count() + 1;
+                        let matched: Vec<usize> = matched_chars[i].iter().filter(|position| **position < visible).map(|position| position + offset).collect();
+
+                        theme.row_with_matches(&format!("{} {}", marker, shown), i == cursor, &matched)
+                    })
+                    .collect()
+            };
+
+            // Selected options might be filtered out, so how many there are is always shown
+            let selected_count = selections.iter().filter(|selected| **selected).count();
+            rows.push(theme.hint(format!("{} selected", selected_count)).to_string());
+
+            draw_rows(out, &rows, &mut drawn_rows);
+
+            // Redraw the prompt line, leaving the cursor after the filter
+            crossterm::queue!(out, crossterm::style::Print("\r"), crossterm::style::Print(input_str), crossterm::style::Print(&filter), crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)).unwrap();
+        }
+    }
+
+    // Clear the list and leave the chosen options on the prompt line
+    draw_rows(out, &[], &mut drawn_rows);
+
+    let chosen: Vec<&str> = labels.iter().zip(&selections).filter(|(_, selected)| **selected).map(|(label, _)| label.as_str()).collect();
+
+    crossterm::queue!(out, crossterm::style::Print("\r"), crossterm::style::Print(input_str), crossterm::style::Print(chosen.join(", ")), crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)).unwrap();
+
+    Ok(selections)
+}
+
+/// Pick a number by stepping it up and down with the arrow keys, shown like `Count: ‹ 5 ›`.
+/// Up and Down change the value by `step`, PageUp and PageDown by ten steps, and the value never goes past `min` or `max`.
+/// A value can also be typed in directly. Starts at `initial`, which is clamped to the bounds.
+/// ## Example
+/// ```no_run
+/// use painless_input::number_stepper;
+///
+/// let count = number_stepper("Count: ", 0, 10, 1, 5);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{number_stepper, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Up)),
+///     Event::Key(KeyEvent::from(KeyCode::PageUp)),
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (count, output) = source.run(|| number_stepper("Count: ", 0u32, 10, 1, 5));
+/// // Clamped at 10 before stepping down
+/// assert_eq!(count, 9);
+/// assert!(output.contains("Count: ‹ "));
+/// ```
+/// Bounds at the limits of the type work too, stepping past them just stays on them:
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{number_stepper, VecEventSource};
+///
+/// let key = |code: KeyCode| Event::Key(KeyEvent::from(code));
+///
+/// let source = VecEventSource::new(vec![key(KeyCode::Up), key(KeyCode::PageDown), key(KeyCode::Enter)]);
+/// let (n, _) = source.run(|| number_stepper("n: ", i32::MIN, i32::MAX, 1, -5));
+/// assert_eq!(n, -14);
+///
+/// let source = VecEventSource::new(vec![key(KeyCode::PageUp), key(KeyCode::Down), key(KeyCode::Enter)]);
+/// let (n, _) = source.run(|| number_stepper("n: ", i32::MIN, i32::MAX, 1, i32::MAX - 3));
+/// assert_eq!(n, i32::MAX - 1);
+///
+/// let source = VecEventSource::new(vec![key(KeyCode::PageDown), key(KeyCode::Up), key(KeyCode::Enter)]);
+/// let (n, _) = source.run(|| number_stepper("n: ", i32::MIN, i32::MAX, 1, i32::MIN + 3));
+/// assert_eq!(n, i32::MIN + 1);
+///
+/// let source = VecEventSource::new(vec![key(KeyCode::Down), key(KeyCode::PageUp), key(KeyCode::Enter)]);
+/// let (n, _) = source.run(|| number_stepper("n: ", 0u64, u64::MAX, u64::MAX / 2, 1));
+/// assert_eq!(n, u64::MAX);
+///
+/// let source = VecEventSource::new(vec![key(KeyCode::Up), key(KeyCode::Enter)]);
+/// let (n, _) = source.run(|| number_stepper("n: ", i64::MIN, 0, 1, i64::MIN));
+/// assert_eq!(n, i64::MIN + 1);
+/// ```
+pub fn number_stepper<T>(input_str: &str, min: T, max: T, step: T, initial: T) -> T
+    where
+        T: Copy + Display + std::ops::Add<Output = T> + std::ops::Sub<Output = T> + PartialOrd + std::str::FromStr,
+{
+    event::with_terminal(|source, out| number_stepper_internal(source, out, input_str, min..=max, step, initial, &Theme::default()))
+}
+
+fn number_stepper_internal<T>(
+    source: &mut dyn EventSource,
+    out: &mut Output,
+    input_str: &str,
+    bounds: std::ops::RangeInclusive<T>,
+    step: T,
+    initial: T,
+    theme: &Theme,
+) -> T
+    where
+        T: Copy + Display + std::ops::Add<Output = T> + std::ops::Sub<Output = T> + PartialOrd + std::str::FromStr,
+{
+    require_interactive(source, "number_stepper");
 
-    // Input data like this
-    // First print [ and then ask for input
-    // Then print , and ask for input
-    // If enter is pressed without any input, it will stop
-    // After that print ]
-    // Example:
-    // [1, 2, 3, 4, 5]
+    let (min, max) = (*bounds.start(), *bounds.end());
 
-    let mut current_input = String::new();
-    let mut result = Vec::new();
-    let mut input_str_vec: Vec<String> = Vec::new();
+    assert!(min <= max, "min must not be more than max");
 
-    // This is used to show error message and delete it correctly when user enters something
-    let mut current_err_msg_len = 0;
+    // Which side of zero the values are on decides how to step without overflowing
+    // The distance from a negative value up to max can be too big for T, but adding a step to it can't be
+    // A type without a zero falls back to min, which always steps using the distance
+    let zero = "0".parse::<T>().unwrap_or(min);
 
-    let validation_closure = if let Some(value) = validation {
-        value
+    let mut value = if initial < min {
+        min
+    } else if initial > max {
+        max
     } else {
-        Box::new(|_: &_| Ok(()))
+        initial
     };
 
+    // What the user is typing, the value is only changed once it parses and is in bounds
+    let mut typed = String::new();
+    let mut error_msg: Option<String> = None;
+
+    // Hide cursor
+    crossterm::queue!(out, crossterm::cursor::Hide).unwrap();
+
+    let mut first_iter = true;
+
     loop {
-        let key_event = crossterm::event::read().unwrap();
+        let mut update = false;
 
-        match key_event {
-            crossterm::event::Event::Key(key) => {
-                if key.kind != crossterm::event::KeyEventKind::Press {
-                    continue;
-                }
+        // If on the first iter, just print and don't wait for input
+        if first_iter {
+            first_iter = false;
+            update = true;
+        } else {
+            let key_event = read_event(source, out);
 
-                match key.code {
-                    crossterm::event::KeyCode::Enter => {
-                        // If final element with no input
-                        if current_input.is_empty() {
-                            // If error message is shown, clear it
-                            if current_err_msg_len > 0 {
-                                clear_right(current_err_msg_len as u16);
-                                current_err_msg_len = 0;
-                            }
+            match key_event {
+                crossterm::event::Event::Key(key) if key.kind == crossterm::event::KeyEventKind::Press => {
+                    if is_interrupt(&key) {
+                        interrupt(out);
+                    }
 
-                            if input_str_vec.len() > 0 {
-                                // Clear the last ", " from terminal
-                                clear_left(2);
-                            }
+                    // Steps up by `steps` steps, checked without overflowing past max
+                    let step_up = |mut value: T, steps: usize| {
+                        for _ in 0..steps {
+                            let fits = if value < zero && max >= zero { value + step <= max } else { max - value >= step };
 
-                            // This is the end so print ]
-                            crossterm::execute!(std::io::stdout(), crossterm::style::Print("]"))
-                                .unwrap();
+                            value = if fits { value + step } else { max };
+                        }
 
-                            std::io::stdout().flush().unwrap();
+                        value
+                    };
 
-                            // Validation
-                            let validation_res = validation_closure(&result);
-                            if validation_res.is_ok() {
-                                break;
-                            } else {
-                                // If input is not valid, show a red bg white text error message after clearing the length of the current_input
+                    // Same as step_up but towards min
+                    let step_down = |mut value: T, steps: usize| {
+                        for _ in 0..steps {
+                            let fits = if value >= zero && min < zero { value - step >= min } else { value - min >= step };
 
-                                // Start with 1 for "]"
-                                let mut clear_amount = 1;
+                            value = if fits { value - step } else { min };
+                        }
+
+                        value
+                    };
 
-                                for (i, input_str) in input_str_vec.iter().enumerate() {
-                                    clear_amount += input_str.len();
+                    match key.code {
+                        crossterm::event::KeyCode::Enter => {
+                            if typed.is_empty() {
+                                break;
+                            }
 
-                                    // if not the last element, add 2 for ", "
-                                    if i != input_str_vec.len() - 1 {
-                                        clear_amount += 2;
-                                    }
+                            match typed.parse::<T>() {
+                                Ok(typed_value) if typed_value >= min && typed_value <= max => {
+                                    value = typed_value;
+                                    break;
+                                }
+                                _ => {
+                                    error_msg = Some(format!("Enter a number between {} and {}", min, max));
+                                    typed.clear();
                                 }
+                            }
+                        }
+                        crossterm::event::KeyCode::Up => value = step_up(value, 1),
+                        crossterm::event::KeyCode::Down => value = step_down(value, 1),
+                        crossterm::event::KeyCode::PageUp => value = step_up(value, 10),
+                        crossterm::event::KeyCode::PageDown => value = step_down(value, 10),
+                        crossterm::event::KeyCode::Backspace => {
+                            typed.pop();
+                        }
+                        crossterm::event::KeyCode::Char(c) if c.is_ascii_digit() || c == '-' || c == '.' => {
+                            typed.push(c);
+                            error_msg = None;
+                        }
+                        _ => continue,
+                    }
 
-                                clear_left(clear_amount as u16);
+                    // Stepping gets rid of anything typed
+                    if matches!(
+                        key.code,
+                        crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Down | crossterm::event::KeyCode::PageUp | crossterm::event::KeyCode::PageDown
+                    ) {
+                        typed.clear();
+                        error_msg = None;
+                    }
 
-                                // crossterm::execute!(std::io::stdout(), crossterm::style::Print("["))
-                                //     .unwrap();
+                    update = true;
+                }
+                crossterm::event::Event::Paste(text) => {
+                    // Only what could be part of a number is kept, the same as what can be typed
+                    typed.extend(pasted_chars(&text).filter(|c| c.is_ascii_digit() || *c == '-' || *c == '.'));
+                    error_msg = None;
 
-                                let error_msg = format!("{}", validation_res.unwrap_err());
+                    update = true;
+                }
+                _ => {}
+            }
+        }
 
-                                error_display(error_msg.as_str(), &mut current_err_msg_len);
+        if update {
+            // Clear line
+            crossterm::queue!(out, crossterm::style::Print("\r")).unwrap();
 
-                                // Start the input again by resetting everything
-                                result.clear();
-                                input_str_vec.clear();
-                                current_input.clear();
+            // Show what's being typed in place of the value
+            let shown = if typed.is_empty() { value.to_string() } else { typed.clone() };
 
-                                continue;
-                            }
-                        }
-                        // If there is input
-                        else {
-                            // Add parsed input to result
-                            let parse_res = current_input.parse::<T>();
+            crossterm::queue!(
+                out,
+                crossterm::style::Print(input_str),
+                crossterm::style::Print("‹ "),
+                crossterm::style::PrintStyledContent(theme.value(shown)),
+                crossterm::style::Print(" ›")
+            )
+                .unwrap();
 
-                            if parse_res.is_ok() {
-                                result.push(parse_res.unwrap());
-                            } else {
-                                // If input is not valid, show a red bg white text error message after clearing the length of the current_input
-                                clear_left(current_input.len() as u16);
+            if let Some(error_msg) = &error_msg {
+                crossterm::queue!(out, crossterm::style::Print("  "), crossterm::style::PrintStyledContent(theme.error(error_msg))).unwrap();
+            }
 
-                                let error_msg =
-                                    format!("Invalid input: '{}'; try again", current_input);
+            crossterm::queue!(out, crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)).unwrap();
+        }
+    }
 
-                                error_display(error_msg.as_str(), &mut current_err_msg_len);
+    // Redraw without the error in case one was shown
+    crossterm::queue!(
+        out,
+        crossterm::style::Print("\r"),
+        crossterm::style::Print(input_str),
+        crossterm::style::Print("‹ "),
+        crossterm::style::PrintStyledContent(theme.value(value)),
+        crossterm::style::Print(" ›"),
+        crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)
+    )
+        .unwrap();
 
-                                current_input.clear();
+    // Show cursor
+    crossterm::queue!(out, crossterm::cursor::Show).unwrap();
 
-                                continue;
-                            }
+    value
+}
 
-                            // Add the current input to input_str_vec
-                            input_str_vec.push(current_input.clone());
+/// Pick a number between `min` and `max` on a bar like `[■■■■□□□□□□] 40`, starting at `initial`.
+/// Left and Right move the value by one, and Home and End jump to the bounds.
+/// The bar is as wide as fits in the terminal, up to 20 columns. Once the value is picked the bar is replaced with just the value.
+/// ## Example
+/// ```no_run
+/// use painless_input::slider;
+///
+/// let volume = slider("Volume: ", 0, 100, 40);
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{slider, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::End)),
+///     Event::Key(KeyEvent::from(KeyCode::Left)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (volume, output) = source.run(|| slider("Volume: ", 0, 10, 5));
+/// assert_eq!(volume, 9);
+/// assert!(output.contains("[■■■■■■■■■■■■■■■■■■■■] 10"));
+/// assert!(output.ends_with("\rVolume: 9\x1b[K\x1b[?25h\x1b[0m\x1b[?25h"));
+/// ```
+/// The whole range of `i64` works too, stepping past the bounds just stays on them:
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{slider, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Home)),
+///     Event::Key(KeyEvent::from(KeyCode::Left)),
+///     Event::Key(KeyEvent::from(KeyCode::End)),
+///     Event::Key(KeyEvent::from(KeyCode::Right)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (value, output) = source.run(|| slider("", i64::MIN, i64::MAX, 0));
+/// assert_eq!(value, i64::MAX);
+/// assert!(output.contains(&format!("] {}", i64::MIN)));
+/// assert!(output.contains(&format!("■] {}", i64::MAX)));
+/// ```
+pub fn slider(input_str: &str, min: i64, max: i64, initial: i64) -> i64 {
+    event::with_terminal(|source, out| slider_internal(source, out, input_str, min, max, initial))
+}
 
-                            // Clear current_input
-                            current_input.clear();
+fn slider_internal(
+    source: &mut dyn EventSource,
+    out: &mut Output,
+    input_str: &str,
+    min: i64,
+    max: i64,
+    initial: i64,
+) -> i64 {
+    require_interactive(source, "slider");
 
-                            // Print ", "
-                            crossterm::execute!(std::io::stdout(), crossterm::style::Print(", "))
-                                .unwrap();
-                        }
-                    }
-                    crossterm::event::KeyCode::Backspace => {
-                        if current_input.is_empty() {
-                            // This means the user wants to delete the last element
-                            // So we pop the last element from the result
-                            if !result.is_empty() {
-                                // If error message is shown, clear it
-                                if current_err_msg_len > 0 {
-                                    clear_right(current_err_msg_len as u16);
-                                    current_err_msg_len = 0;
-                                }
+    assert!(min <= max, "min must not be more than max");
 
-                                result.pop();
+    let mut value = initial.clamp(min, max);
 
-                                // clear the ", " from terminal
-                                clear_left(2);
+    // Leave room for the prompt, the brackets and the widest value after the bar
+    let label_width = min.to_string().len().max(max.to_string().len());
+    let room = (out.width() as usize).saturating_sub(display_width(input_str) as usize + label_width + 4);
+    let bar_width = room.clamp(1, 20);
 
-                                // delete the last input_str_vec and clear it from terminal
-                                let chars_to_clear = input_str_vec.pop().unwrap().len();
+    // Hide cursor
+    crossterm::queue!(out, crossterm::cursor::Hide).unwrap();
 
-                                clear_left(chars_to_clear as u16);
+    let mut first_iter = true;
 
-                                std::io::stdout().flush().unwrap();
-                            }
-                        } else {
-                            // This means just delete the last character from current_input
-                            current_input.pop();
-                            // Then delete from terminal
-                            clear_left(1);
-                        }
+    loop {
+        let mut update = false;
+
+        // If on the first iter, just print and don't wait for input
+        if first_iter {
+            first_iter = false;
+            update = true;
+        } else {
+            let key_event = read_event(source, out);
+
+            match key_event {
+                crossterm::event::Event::Key(key) if key.kind == crossterm::event::KeyEventKind::Press => {
+                    if is_interrupt(&key) {
+                        interrupt(out);
                     }
-                    crossterm::event::KeyCode::Char(c) => {
-                        // If error message is shown, clear it
-                        if current_err_msg_len > 0 {
-                            clear_right(current_err_msg_len as u16);
-                            current_err_msg_len = 0;
-                        }
 
-                        current_input.push(c);
-                        crossterm::execute!(std::io::stdout(), crossterm::style::Print(c)).unwrap();
-                        std::io::stdout().flush().unwrap();
+                    match key.code {
+                        crossterm::event::KeyCode::Enter => {
+                            break;
+                        }
+                        crossterm::event::KeyCode::Left => value = value.saturating_sub(1).max(min),
+                        crossterm::event::KeyCode::Right => value = value.saturating_add(1).min(max),
+                        crossterm::event::KeyCode::Home => value = min,
+                        crossterm::event::KeyCode::End => value = max,
+                        _ => continue,
                     }
-                    _ => {}
+
+                    update = true;
                 }
+                _ => {}
             }
-            _ => {}
         }
-    }
 
-    result
-}
+        if update {
+            // How much of the bar is filled, all of it if there's only one possible value
+            let filled = if max == min {
+                bar_width
+            } else {
+                // Work in i128 so the distance across a range like i64::MIN..=i64::MAX can't overflow
+                let (value, min, max) = (value as i128, min as i128, max as i128);
+                ((value - min) as f64 / (max - min) as f64 * bar_width as f64).round() as usize
+            };
 
+            // Clear line
+            crossterm::queue!(out, crossterm::style::Print("\r")).unwrap();
 
-fn error_display(error_msg: &str, error_len_var: &mut usize) {
-    // Make it red text and red underline
-    crossterm::execute!(
-        std::io::stdout(),
-        crossterm::style::Print("\x1b[41;31;4m"),
-        crossterm::style::Print(&error_msg),
-        crossterm::style::Print("\x1b[0m")
-    )
-        .unwrap();
+            crossterm::queue!(
+                out,
+                crossterm::style::Print(input_str),
+                crossterm::style::Print(format!("[{}{}] {}", "■".repeat(filled), "□".repeat(bar_width - filled), value)),
+                crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)
+            )
+                .unwrap();
+        }
+    }
 
-    // move cursor left
-    crossterm::execute!(
-        std::io::stdout(),
-        crossterm::cursor::MoveLeft(error_msg.len() as u16)
+    // Replace the bar with just the value
+    crossterm::queue!(
+        out,
+        crossterm::style::Print("\r"),
+        crossterm::style::Print(input_str),
+        crossterm::style::Print(value),
+        crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)
     )
         .unwrap();
-    // flush stdout
-    std::io::stdout().flush().unwrap();
 
-    *error_len_var = error_msg.len();
+    // Show cursor
+    crossterm::queue!(out, crossterm::cursor::Show).unwrap();
+
+    value
 }
 
+/// The number of columns `input` takes up on screen, which is one mask per character if it's masked.
+/// Wide characters like CJK take up two columns.
+fn displayed_len(input: &str, mask: Option<char>) -> usize {
+    match mask {
+        Some(mask) => input.chars().count() * mask.to_string().width(),
+        None => input.width(),
+    }
+}
 
-const UP_DOWN_ARROW: &str = "⭥";
+/// The characters of pasted `text` that can be added to a single line input.
+/// Newlines would submit the input if they were typed, so they become spaces, and other control characters are dropped.
+fn pasted_chars(text: &str) -> impl Iterator<Item = char> + '_ {
+    text.chars()
+        .map(|c| if c == '\n' { ' ' } else { c })
+        .filter(|c| !c.is_control())
+}
 
-/// Select an input from the user using arrow keys.
-/// The input will look like this
-/// Choose an option: [Test]⭥
-/// Click the up and down arrows to navigate, enter to submit
-pub fn select_input<T>(input_str: &str, options: &[T]) -> usize
-    where T: Display
-{
-    // Hide cursor
-    crossterm::execute!(std::io::stdout(), crossterm::cursor::Hide).unwrap();
+/// A yes or no answer typed in any case, or `None` if it's neither
+fn parse_bool(answer: &str) -> Option<bool> {
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" | "true" | "1" => Some(true),
+        "n" | "no" | "false" | "0" => Some(false),
+        _ => None,
+    }
+}
 
-    let mut cursor = 0;
-    let mut longest_option = 0;
+/// A duration written as numbers each followed by a unit, like `90s`, `1.5h` or `1h 30m`, or the message to show if it isn't one.
+/// The units are `ms`, `s`, `m`, `h` and `d`, and spaces between the parts are ignored.
+fn parse_duration(text: &str) -> Result<Duration, String> {
+    const HINT: &str = "Enter a duration like 30s, 5m or 1h30m";
 
-    for option in options {
-        let option_len = format!("{}", option).len();
-        if option_len > longest_option {
-            longest_option = option_len;
-        }
+    let mut rest = text.trim();
+
+    if rest.is_empty() {
+        return Err(String::from(HINT));
     }
 
-    crossterm::execute!(std::io::stdout(), crossterm::style::Print(input_str), crossterm::style::Print("\x1b[1m"), crossterm::style::Print("["), crossterm::style::Print(format!("{}", options[0])), crossterm::style::Print("]"), crossterm::style::Print(UP_DOWN_ARROW), crossterm::style::Print("\x1b[0m")).unwrap();
+    let mut total = Duration::ZERO;
 
-    stdout().flush().unwrap();
+    while !rest.is_empty() {
+        let number_len = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+        let (number, after) = rest.split_at(number_len);
 
-    loop {
-        let key_event = crossterm::event::read().unwrap();
-        let mut to_update = false;
+        let Ok(number) = number.parse::<f64>() else {
+            return Err(String::from(HINT));
+        };
 
-        match key_event {
-            crossterm::event::Event::Key(key) => {
-                if key.kind != crossterm::event::KeyEventKind::Press {
-                    continue;
-                }
+        let after = after.trim_start();
+        let unit_len = after.find(|c: char| !c.is_alphabetic()).unwrap_or(after.len());
+        let (unit, after) = after.split_at(unit_len);
 
-                match key.code {
-                    crossterm::event::KeyCode::Enter => {
-                        break;
-                    }
-                    crossterm::event::KeyCode::Up => {
-                        if cursor > 0 {
-                            cursor -= 1;
-                        }
+        let unit_secs = match unit {
+            "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            "d" => 86400.0,
+            "" => return Err(format!("'{}' needs a unit, like {}s or {}m", number, number, number)),
+            _ => return Err(format!("'{}' isn't a unit, use ms, s, m, h or d", unit)),
+        };
 
-                        to_update = true;
-                    }
-                    crossterm::event::KeyCode::Down => {
-                        if cursor < options.len() - 1 {
-                            cursor += 1;
-                        }
+        total = Duration::try_from_secs_f64(number * unit_secs)
+            .ok()
+            .and_then(|part| total.checked_add(part))
+            .ok_or_else(|| String::from("That duration is too long"))?;
 
-                        to_update = true;
-                    }
-                    _ => {}
-                }
-            }
-            _ => {}
-        }
+        rest = after.trim_start();
+    }
 
-        if to_update {
-            // Clear line
-            crossterm::execute!(std::io::stdout(), crossterm::style::Print("\r")).unwrap();
+    Ok(total)
+}
 
-            // Print input_str
-            crossterm::execute!(std::io::stdout(), crossterm::style::Print(input_str), crossterm::style::Print("\x1b[1m"), crossterm::style::Print("[")).unwrap();
+/// `path` with a leading `~` replaced by the home directory, or unchanged if there's no home directory
+fn expand_tilde(path: &std::path::Path) -> std::path::PathBuf {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
 
-            // Clear enough to get rid of everything on the right
-            // +1 for the ]
-            clear_right(longest_option as u16 + UP_DOWN_ARROW.len() as u16 + 1);
+    match (path.strip_prefix("~"), home) {
+        (Ok(rest), Some(home)) => std::path::PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
 
-            // Print the option
-            crossterm::execute!(std::io::stdout(), crossterm::style::Print(&options[cursor]), crossterm::style::Print("]"), crossterm::style::Print(UP_DOWN_ARROW), crossterm::style::Print("\x1b[0m")).unwrap();
+/// `text` cut short with a `…` if it's wider than `width` columns
+fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
 
-            std::io::stdout().flush().unwrap();
+    let mut truncated = String::new();
+
+    // Leave a column for the …
+    for grapheme in text.graphemes(true) {
+        if truncated.width() + grapheme.width() + 1 > width {
+            break;
         }
+
+        truncated.push_str(grapheme);
     }
 
-    // Show cursor
-    crossterm::execute!(std::io::stdout(), crossterm::cursor::Show).unwrap();
+    if width > 0 {
+        truncated.push('…');
+    }
 
-    cursor
+    truncated
 }
 
-const CONFIRM_TICK: &str = "✓";
+/// Remove the last grapheme from `input` and return it.
+/// A grapheme can be made of multiple chars, like an emoji with a skin tone modifier, but is still deleted as one.
+fn pop_grapheme(input: &mut String) -> Option<String> {
+    let grapheme = input.graphemes(true).next_back()?.to_string();
 
-// These two must be the same length
-const SELECTED: &str = "☑";
-const UNSELECTED: &str = "☐";
+    input.truncate(input.len() - grapheme.len());
 
-pub fn multiselect_input(input_str: &str, submit_str: &str, options: &[&str]) -> Vec<bool> {
-    let mut cursor = 0;
+    Some(grapheme)
+}
 
-    let mut selections = Vec::new();
-    selections.resize(options.len(), false);
+/// Show everything queued up for this frame, then wait for the next event.
+/// Drawing is queued up rather than written straight away, so this is the only place that flushes while a prompt is open.
+/// Running out of events, like when piped stdin is closed, leaves the prompt with nothing to answer it so it panics.
+fn read_event(source: &mut dyn EventSource, out: &mut Output) -> crossterm::event::Event {
+    out.flush().unwrap();
 
-    // Hide cursor
-    crossterm::execute!(std::io::stdout(), crossterm::cursor::Hide).unwrap();
+    match source.read() {
+        Ok(event) => event,
+        Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => panic!("stdin closed before a valid answer was given"),
+        Err(err) => panic!("couldn't read from the terminal: {}", err),
+    }
+}
 
-    // Print input_str as bold
-    crossterm::execute!(std::io::stdout(), crossterm::style::Print("\x1b[1m"), crossterm::style::Print(input_str.trim()), crossterm::style::Print("\x1b[0m")).unwrap();
-    crossterm::execute!(std::io::stdout(), crossterm::style::Print("\n")).unwrap();
+/// The list widgets are only driven by arrow keys, so they can't be answered with piped input like the text prompts can
+fn require_interactive(source: &dyn EventSource, widget: &str) {
+    assert!(source.is_interactive(), "{} needs an interactive terminal, but stdin isn't one", widget);
+}
 
-    let mut lines: Vec<String> = Vec::new();
+/// Whether the key press is Ctrl+C
+fn is_interrupt(key: &crossterm::event::KeyEvent) -> bool {
+    key.code == crossterm::event::KeyCode::Char('c') && key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL)
+}
 
-    for option in options {
-        lines.push(format!("{} {}", UNSELECTED, option));
+/// Put the terminal back to normal after Ctrl+C and exit with the status SIGINT would have given
+fn interrupt(out: &mut Output) -> ! {
+    if !theme::no_color() {
+        crossterm::execute!(out, crossterm::style::ResetColor).unwrap();
     }
 
-    // Move cursor to the first char
-    crossterm::execute!(std::io::stdout(), crossterm::style::Print("\r")).unwrap();
+    out.disable_bracketed_paste();
 
-    stdout().flush().unwrap();
+    #[cfg(feature = "mouse")]
+    out.disable_mouse_capture();
 
-    let mut first_iter = true;
+    crossterm::execute!(out, crossterm::cursor::Show, crossterm::style::Print("\r\n")).unwrap();
 
-    loop {
-        let mut update = false;
+    // Guards aren't dropped when exiting, and raw mode shouldn't outlive the program even if the caller turned it on
+    let _ = crossterm::terminal::disable_raw_mode();
 
-        // If on the first iter, just print and don't wait for input
-        if first_iter {
-            first_iter = false;
-            update = true;
-        } else {
-            let key_event = crossterm::event::read().unwrap();
+    std::process::exit(130);
+}
 
-            match key_event {
-                crossterm::event::Event::Key(key) => {
-                    if key.kind != crossterm::event::KeyEventKind::Press {
-                        match key.code {
-                            crossterm::event::KeyCode::Enter => {
-                                // If at the submit button
-                                if cursor >= options.len() {
-                                    break;
-                                }
-                                // If at an option
-                                else {
-                                    selections[cursor] = !selections[cursor];
+/// Draw `rows` on the lines below the current one, clearing any rows left over from the last draw.
+/// `drawn_rows` keeps track of how many rows are on screen. The cursor is moved back to the current line afterwards.
+fn draw_rows(out: &mut Output, rows: &[String], drawn_rows: &mut usize) {
+    for row in rows {
+        // \r\n instead of MoveDown so the terminal scrolls if the rows reach the bottom
+        crossterm::queue!(out, crossterm::style::Print("\r\n"), crossterm::style::Print(row), crossterm::terminal::Clear(crossterm::terminal::ClearType::UntilNewLine)).unwrap();
+    }
 
-                                    lines[cursor] = if selections[cursor] {
-                                        format!("{} {}", SELECTED, options[cursor])
-                                    } else {
-                                        format!("{} {}", UNSELECTED, options[cursor])
-                                    };
+    // Clear rows left over from the last draw
+    for _ in rows.len()..*drawn_rows {
+        crossterm::queue!(out, crossterm::style::Print("\r\n"), crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine)).unwrap();
+    }
 
-                                    update = true;
-                                }
-                            },
-                            crossterm::event::KeyCode::Down => {
-                                // If at the submit button
-                                if cursor == options.len() {
-                                    // Move to first option
-                                    crossterm::execute!(std::io::stdout(), crossterm::cursor::MoveUp(options.len() as u16)).unwrap();
-
-                                    cursor = 0;
-                                }
-                                // If at an option
-                                else {
-                                    // Move down
-                                    crossterm::execute!(std::io::stdout(), crossterm::cursor::MoveDown(1)).unwrap();
+    let rows_moved = rows.len().max(*drawn_rows);
 
-                                    cursor += 1;
-                                }
+    // MoveUp still moves if it receives 0
+    if rows_moved > 0 {
+        crossterm::queue!(out, crossterm::cursor::MoveUp(rows_moved as u16)).unwrap();
+    }
 
-                                update = true;
-                            },
-                            crossterm::event::KeyCode::Up => {
-                                // If at the first option
-                                if cursor == 0 {
-                                    // Move to submit button
-                                    crossterm::execute!(std::io::stdout(), crossterm::cursor::MoveDown(options.len() as u16)).unwrap();
-
-                                    cursor = options.len();
-                                }
-                                // If at an option
-                                else {
-                                    // Move up
-                                    crossterm::execute!(std::io::stdout(), crossterm::cursor::MoveUp(1)).unwrap();
+    *drawn_rows = rows.len();
+}
 
-                                    cursor -= 1;
-                                }
+/// The message to show after line `index`, if it belongs to that line
+fn line_message(theme: &Theme, message: &Option<(usize, String)>, index: usize) -> String {
+    match message {
+        Some((message_index, message)) if *message_index == index => format!("  {}", theme.error(message)),
+        _ => String::new(),
+    }
+}
 
-                                update = true;
-                            },
-                            _ => {}
-                        }
-                    }
+/// Ring the terminal bell for a rejected input, if the theme has it turned on
+fn ring_bell(out: &mut Output, theme: &Theme) {
+    // BEL makes the terminal beep or flash, it isn't printed so it doesn't move the cursor
+    if theme.bell {
+        crossterm::queue!(out, crossterm::style::Print('\x07')).unwrap();
+    }
+}
 
-                }
-                _ => {}
-            }
-        }
+/// Print `text` starting `offset` columns into a line, moving `offset` on past it. The line wraps onto more rows if it's wider than the terminal.
+/// A terminal leaves the cursor at the end of a full row until something else is printed, so the next row is started straight away
+/// to keep the cursor's row the same however it got there. `style` is applied to the part of `text` on each row.
+fn print_wrapping(out: &mut Output, text: &str, offset: &mut usize, style: impl Fn(&str) -> String) {
+    let width = out.width().max(1) as usize;
+    let mut row_text = String::new();
 
-        if update {
-            // Move cursor to first option
-            // The if is required because if cursor is at 0, it will move up 1 which is not what we want
-            if cursor > 0 {
-                crossterm::execute!(std::io::stdout(), crossterm::cursor::MoveUp(cursor as u16)).unwrap();
-            }
+    let mut rest = text;
 
-            for (i, line) in lines.iter().enumerate() {
-                // Clear line
-                crossterm::execute!(std::io::stdout(), crossterm::style::Print("\r")).unwrap();
+    while let Some(grapheme) = rest.graphemes(true).next() {
+        // Escape sequences in the text, like styling, are printed along with it but don't take up any room
+        if let Some(len) = width::escape_len(rest) {
+            row_text.push_str(&rest[..len]);
+            rest = &rest[len..];
+            continue;
+        }
 
-                // Print line
-                if i == cursor {
-                    // Underline if cursor is on line
-                    crossterm::execute!(std::io::stdout(), crossterm::style::Print("\x1b[4m"), crossterm::style::Print(line), crossterm::style::Print("\x1b[0m")).unwrap();
-                } else {
-                    crossterm::execute!(std::io::stdout(), crossterm::style::Print(line)).unwrap();
-                }
+        row_text.push_str(grapheme);
+        rest = &rest[grapheme.len()..];
+        *offset += grapheme.width();
 
-                // Move to next line
-                crossterm::execute!(std::io::stdout(), crossterm::cursor::MoveDown(1)).unwrap();
-            }
+        if offset.is_multiple_of(width) {
+            crossterm::queue!(out, crossterm::style::Print(style(&row_text)), crossterm::style::Print("\r\n")).unwrap();
+            row_text.clear();
+        }
+    }
 
-            // Submit button
-            if cursor == options.len() {
-                // Clear line
-                crossterm::execute!(std::io::stdout(), crossterm::style::Print("\r")).unwrap();
+    if !row_text.is_empty() {
+        crossterm::queue!(out, crossterm::style::Print(style(&row_text))).unwrap();
+    }
+}
 
-                // Print submit button as bold and underlined
-                crossterm::execute!(std::io::stdout(), crossterm::style::Print("\x1b[1;4m"), crossterm::style::Print(format!("{} {}", CONFIRM_TICK, submit_str)), crossterm::style::Print("\x1b[0m")).unwrap();
-            } else {
-                // Clear line
-                crossterm::execute!(std::io::stdout(), crossterm::style::Print("\r")).unwrap();
+/// Move the cursor back from `from` columns into a line to `to`, going up any rows the line has wrapped onto in between
+fn move_back(out: &mut Output, from: usize, to: usize) {
+    let width = out.width().max(1) as usize;
+    let rows_up = from / width - to / width;
 
-                // Print submit button as bold
-                crossterm::execute!(std::io::stdout(), crossterm::style::Print("\x1b[1m"), crossterm::style::Print(format!("{} {}", CONFIRM_TICK, submit_str)), crossterm::style::Print("\x1b[0m")).unwrap();
-            }
+    if rows_up > 0 {
+        crossterm::queue!(out, crossterm::cursor::MoveUp(rows_up as u16)).unwrap();
+    }
 
-            // Move cursor back to cursor line
-            let move_up_to_return = options.len() as u16 - cursor as u16;
+    crossterm::queue!(out, crossterm::cursor::MoveToColumn((to % width) as u16)).unwrap();
+}
 
-            if move_up_to_return > 0 {
-                // MoveUp still moves if it receives 0
-                crossterm::execute!(std::io::stdout(), crossterm::cursor::MoveUp(move_up_to_return)).unwrap();
-            }
+/// Move the cursor on from `from` columns into a line to `to`, going down any rows the line has wrapped onto in between
+fn move_forward(out: &mut Output, from: usize, to: usize) {
+    if from == to {
+        return;
+    }
 
-            // Carriage return
-            crossterm::execute!(std::io::stdout(), crossterm::style::Print("\r")).unwrap();
+    let width = out.width().max(1) as usize;
+    let rows_down = to / width - from / width;
 
-            // Flush stdout
-            std::io::stdout().flush().unwrap();
-        }
+    if rows_down > 0 {
+        crossterm::queue!(out, crossterm::cursor::MoveDown(rows_down as u16)).unwrap();
     }
 
-    // Show cursor
-    crossterm::execute!(std::io::stdout(), crossterm::cursor::Show).unwrap();
+    crossterm::queue!(out, crossterm::cursor::MoveToColumn((to % width) as u16)).unwrap();
+}
+
+/// Same as `clear_right`, but for a cursor `start` columns into a line that may have wrapped onto more rows
+fn clear_right_wrapping(out: &mut Output, start: usize, chars: usize) {
+    let mut offset = start;
 
-    selections
+    print_wrapping(out, &" ".repeat(chars), &mut offset, str::to_string);
+    move_back(out, offset, start);
 }
 
-fn clear_left(chars: u16) {
+fn clear_left(out: &mut Output, chars: u16) {
     for _ in 0..chars {
-        crossterm::execute!(std::io::stdout(), crossterm::cursor::MoveLeft(1)).unwrap();
-        crossterm::execute!(std::io::stdout(), crossterm::style::Print(" ")).unwrap();
-        crossterm::execute!(std::io::stdout(), crossterm::cursor::MoveLeft(1)).unwrap();
+        crossterm::queue!(out, crossterm::cursor::MoveLeft(1)).unwrap();
+        crossterm::queue!(out, crossterm::style::Print(" ")).unwrap();
+        crossterm::queue!(out, crossterm::cursor::MoveLeft(1)).unwrap();
     }
-
-    std::io::stdout().flush().unwrap();
 }
 
-fn clear_right(chars: u16) {
+fn clear_right(out: &mut Output, chars: u16) {
     for _ in 0..chars {
-        crossterm::execute!(std::io::stdout(), crossterm::style::Print(" ")).unwrap();
+        crossterm::queue!(out, crossterm::style::Print(" ")).unwrap();
     }
 
-    crossterm::execute!(std::io::stdout(), crossterm::cursor::MoveLeft(chars)).unwrap();
-
-    std::io::stdout().flush().unwrap();
+    crossterm::queue!(out, crossterm::cursor::MoveLeft(chars)).unwrap();
 }
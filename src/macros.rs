@@ -0,0 +1,40 @@
+/// Shorthand for [`input`](crate::input) that takes the type to parse as a second argument instead of needing an annotation.
+/// With just a prompt the type is inferred, the same as calling `input`.
+/// ## Example
+/// ```no_run
+/// use painless_input::input;
+///
+/// let num = input!("Enter a number: ", i32);
+/// println!();
+///
+/// let name: String = input!("Enter your name: ");
+/// println!();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('7'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('8'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let ((num, inferred), _) = source.run(|| {
+///     let num = input!("Enter a number: ", u8);
+///     let inferred: f64 = input!("Enter another number: ");
+///     (num, inferred)
+/// });
+/// assert_eq!(num, 7u8);
+/// assert_eq!(inferred, 8.0);
+/// ```
+#[macro_export]
+macro_rules! input {
+    ($input_str:expr, $type:ty $(,)?) => {
+        $crate::input::<$type>($input_str)
+    };
+    ($input_str:expr $(,)?) => {
+        $crate::input($input_str)
+    };
+}
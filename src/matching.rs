@@ -0,0 +1,143 @@
+/// The longest prefix that all of `candidates` start with, taken from the first candidate.
+/// With `ignore_case`, characters that only differ in case count as the same, like `Apple` and `apricot` sharing `Ap`.
+/// This is how Tab in `input_autocomplete` works out how much it can fill in.
+/// ## Example
+/// ```
+/// use painless_input::common_prefix;
+///
+/// assert_eq!(common_prefix(&[], false), "");
+/// assert_eq!(common_prefix(&["apple"], false), "apple");
+/// assert_eq!(common_prefix(&["apple", "apricot", "april"], false), "ap");
+/// assert_eq!(common_prefix(&["apple", "banana"], false), "");
+///
+/// assert_eq!(common_prefix(&["Apple", "apricot"], false), "");
+/// assert_eq!(common_prefix(&["Apple", "apricot"], true), "Ap");
+/// ```
+pub fn common_prefix(candidates: &[&str], ignore_case: bool) -> String {
+    let Some(first) = candidates.first() else {
+        return String::new();
+    };
+
+    let same = |a: char, b: char| {
+        if ignore_case {
+            a.to_lowercase().eq(b.to_lowercase())
+        } else {
+            a == b
+        }
+    };
+
+    let mut len = first.len();
+
+    for candidate in &candidates[1..] {
+        len = first
+            .char_indices()
+            .zip(candidate.chars())
+            .take_while(|((i, a), b)| *i < len && same(*a, *b))
+            .last()
+            .map(|((i, a), _)| i + a.len_utf8())
+            .unwrap_or(0);
+    }
+
+    first[..len].to_string()
+}
+
+/// Where and how well a pattern fuzzy matched a candidate, see `fuzzy_match`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher is a better match, it's only meaningful compared to the scores of other candidates for the same pattern
+    pub score: i64,
+    /// Indices of the chars of the candidate that matched the pattern, in order
+    pub positions: Vec<usize>,
+}
+
+/// Match `pattern` against `candidate` the way `fzf` does: every char of the pattern has to appear in the candidate in order, ignoring case, but not next to each other.
+/// Returns `None` if it doesn't match. Matches with the chars close together and at the starts of words score higher.
+/// An empty pattern matches everything with a score of 0.
+/// ## Example
+/// ```
+/// use painless_input::fuzzy_match;
+///
+/// let found = fuzzy_match("opt3", "Option 3").unwrap();
+/// assert_eq!(found.positions, vec![0, 1, 2, 7]);
+///
+/// assert!(fuzzy_match("opt3", "Option 4").is_none());
+///
+/// // Starting at a word and staying together beats being spread out
+/// let options = fuzzy_match("op", "Options").unwrap();
+/// let loop_time = fuzzy_match("op", "Loop time").unwrap();
+/// assert!(options.score > loop_time.score);
+/// ```
+pub fn fuzzy_match(pattern: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let chars: Vec<char> = candidate.chars().collect();
+
+    let same = |a: char, b: char| a.to_lowercase().eq(b.to_lowercase());
+
+    if pattern.is_empty() {
+        return Some(FuzzyMatch { score: 0, positions: Vec::new() });
+    }
+
+    // Find where the earliest match ends
+    let mut matched = 0;
+    let mut end = 0;
+
+    for (i, c) in chars.iter().enumerate() {
+        if same(*c, pattern[matched]) {
+            matched += 1;
+
+            if matched == pattern.len() {
+                end = i + 1;
+                break;
+            }
+        }
+    }
+
+    if matched < pattern.len() {
+        return None;
+    }
+
+    // Then go backwards from there, which finds the latest start and so the tightest match ending there
+    let mut start = 0;
+
+    for i in (0..end).rev() {
+        if same(chars[i], pattern[matched - 1]) {
+            matched -= 1;
+
+            if matched == 0 {
+                start = i;
+                break;
+            }
+        }
+    }
+
+    let mut positions = Vec::new();
+
+    for (i, c) in chars.iter().enumerate().take(end).skip(start) {
+        if positions.len() < pattern.len() && same(*c, pattern[positions.len()]) {
+            positions.push(i);
+        }
+    }
+
+    let mut score = 0;
+
+    for (n, &i) in positions.iter().enumerate() {
+        score += 16;
+
+        // The start of a word, after a space or punctuation or in camelCase
+        if i == 0 || !chars[i - 1].is_alphanumeric() || (chars[i - 1].is_lowercase() && chars[i].is_uppercase()) {
+            score += 8;
+        }
+
+        if n > 0 {
+            let gap = (i - positions[n - 1] - 1) as i64;
+
+            if gap == 0 {
+                score += 8;
+            } else {
+                score -= gap;
+            }
+        }
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
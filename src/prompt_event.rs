@@ -0,0 +1,56 @@
+/// Something that happened in a text prompt, passed to the callback given to `InputBuilder::on_event`.
+/// Masked prompts, like passwords, pass on the mask instead of what was typed, so the callback never sees the secret.
+/// ## Example
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{InputBuilder, PromptEvent, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('x'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('4'))),
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('5'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let events = Rc::new(RefCell::new(Vec::new()));
+/// let log = Rc::clone(&events);
+///
+/// let (num, _) = source.run(|| {
+///     InputBuilder::<i32>::new()
+///         .prompt("Enter a number: ")
+///         .on_event(move |event| log.borrow_mut().push(event))
+///         .run()
+/// });
+/// assert_eq!(num.unwrap(), 5);
+/// assert_eq!(*events.borrow(), vec![
+///     PromptEvent::CharTyped('x'),
+///     PromptEvent::ValidationFailed(String::from("Invalid input: 'x'; try again")),
+///     PromptEvent::CharTyped('4'),
+///     PromptEvent::Deleted,
+///     PromptEvent::CharTyped('5'),
+///     PromptEvent::Submitted(String::from("5")),
+/// ]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PromptEvent {
+    /// A char was typed or pasted into the input
+    CharTyped(char),
+    /// Some of the input was deleted, with Backspace, Delete, Ctrl+U or Ctrl+K
+    Deleted,
+    /// The input was submitted but didn't parse or pass validation, with the error message shown
+    ValidationFailed(String),
+    /// The input was accepted, with the text that was typed
+    Submitted(String),
+    /// The user backed out of the prompt
+    Cancelled,
+    /// The prompt's timeout ran out
+    TimedOut,
+    /// The input was rejected as many times as the prompt allows
+    TooManyAttempts,
+}
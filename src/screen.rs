@@ -0,0 +1,111 @@
+use std::fmt::Display;
+
+use crate::{event, Theme};
+
+/// Draws each prompt of a multi-step flow at the top of a cleared screen, so the answers don't scroll away in a long wizard.
+/// The answers given so far are listed above the current prompt as a summary, which can be turned off with `summary(false)`.
+/// ## Example
+/// ```no_run
+/// use painless_input::{input, select_input, Screen};
+///
+/// let mut screen = Screen::new();
+///
+/// let name: String = screen.ask("Name: ", |prompt| input(prompt));
+/// let size = screen.ask("Size: ", |prompt| ["Small", "Large"][select_input(prompt, &["Small", "Large"]).unwrap()]);
+/// screen.clear();
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input, Screen, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('A'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('7'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let ((name, age), output) = source.run(|| {
+///     let mut screen = Screen::new();
+///
+///     let name: String = screen.ask("Name: ", |prompt| input(prompt));
+///     let age: u8 = screen.ask("Age: ", |prompt| input(prompt));
+///
+///     (name, age)
+/// });
+/// assert_eq!((name.as_str(), age), ("A", 7));
+///
+/// // The screen is cleared before each prompt, and the second one has the first answer above it
+/// let second = output.split("\x1b[2J\x1b[1;1H").nth(2).unwrap();
+/// assert!(second.starts_with("Name: \x1b[1mA\x1b[0m\r\n"));
+/// assert!(second.contains("Age: 7"));
+/// ```
+pub struct Screen {
+    /// Each prompt asked so far with its answer
+    answers: Vec<(String, String)>,
+    summary: bool,
+    theme: Theme,
+}
+
+impl Screen {
+    pub fn new() -> Self {
+        Self {
+            answers: Vec::new(),
+            summary: true,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Whether the answers given so far are shown above each prompt, on by default
+    pub fn summary(mut self, summary: bool) -> Self {
+        self.summary = summary;
+        self
+    }
+
+    /// The theme the summary is drawn with
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Clear the screen and draw the summary, leaving the cursor at the start of the line after it.
+    /// Called by `ask` before each prompt, and useful once the flow is finished to leave just the summary behind.
+    pub fn clear(&self) {
+        event::with_terminal(|_, out| {
+            crossterm::queue!(out, crossterm::terminal::Clear(crossterm::terminal::ClearType::All), crossterm::cursor::MoveTo(0, 0)).unwrap();
+
+            if self.summary {
+                for (prompt, answer) in &self.answers {
+                    crossterm::queue!(
+                        out,
+                        crossterm::style::Print(prompt),
+                        crossterm::style::PrintStyledContent(self.theme.value(answer)),
+                        crossterm::style::Print("\r\n")
+                    )
+                        .unwrap();
+                }
+            }
+        });
+    }
+
+    /// Clear the screen, then run the prompt `f` with `prompt` and add its answer to the summary.
+    pub fn ask<R, F>(&mut self, prompt: &str, f: F) -> R
+        where
+            R: Display,
+            F: FnOnce(&str) -> R,
+    {
+        self.clear();
+
+        let answer = f(prompt);
+
+        self.answers.push((prompt.to_string(), answer.to_string()));
+
+        answer
+    }
+}
+
+impl Default for Screen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
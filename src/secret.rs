@@ -0,0 +1,146 @@
+use std::fmt;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::event::{self, EventSource, Output};
+use crate::{interrupt, is_interrupt, read_event, Theme};
+
+/// A string that's wiped from memory when it's dropped, returned by [`prompt_secret`].
+/// It can't be printed by accident, `Debug` shows `[hidden]`, and `expose_secret` has to be called to read it.
+pub struct SecretString {
+    /// Always valid UTF-8, the bytes past the end are kept zeroed so growing or shrinking never leaves a copy behind
+    bytes: Vec<u8>,
+}
+
+impl SecretString {
+    fn new() -> Self {
+        Self { bytes: Vec::new() }
+    }
+
+    /// The secret itself, which shouldn't be kept around any longer than needed.
+    pub fn expose_secret(&self) -> &str {
+        std::str::from_utf8(&self.bytes).unwrap()
+    }
+
+    fn push(&mut self, c: char) {
+        let mut encoded = [0; 4];
+        let encoded = c.encode_utf8(&mut encoded).as_bytes();
+
+        // Grow by hand, letting the Vec reallocate would free the old buffer without wiping it
+        if self.bytes.len() + encoded.len() > self.bytes.capacity() {
+            let mut grown = Vec::with_capacity((self.bytes.capacity() * 2).max(self.bytes.len() + encoded.len()).max(32));
+            grown.extend_from_slice(&self.bytes);
+
+            wipe(&mut self.bytes);
+            self.bytes = grown;
+        }
+
+        self.bytes.extend_from_slice(encoded);
+    }
+
+    /// Remove the last grapheme, wiping its bytes
+    fn pop_grapheme(&mut self) {
+        if let Some((start, _)) = self.expose_secret().grapheme_indices(true).next_back() {
+            wipe(&mut self.bytes[start..]);
+            self.bytes.truncate(start);
+        }
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        wipe(&mut self.bytes);
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretString([hidden])")
+    }
+}
+
+/// Overwrite `bytes` with zeros in a way the compiler can't optimize out, even though they're about to be freed
+fn wipe(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        // SAFETY: `byte` is a valid, aligned reference to a u8
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+/// Input a secret from the user, like a passphrase, without anything of it ever being written to the terminal.
+/// Unlike `input_password` no mask is drawn either, so not even the length of the secret shows, just a dimmed `[hidden]` after the prompt.
+/// Backspace deletes the last character typed and Enter submits.
+///
+/// The secret is kept in a [`SecretString`], which wipes it from memory when dropped.
+/// The buffer is grown by hand so no copies are left behind as it grows, and pasted text is wiped once it's been added.
+/// Piped input goes through stdin's own buffers first, which can't be wiped.
+/// ## Example
+/// ```no_run
+/// use painless_input::prompt_secret;
+///
+/// let passphrase = prompt_secret("Passphrase: ");
+/// println!();
+/// # fn unlock(_: &str) {}
+/// unlock(passphrase.expose_secret());
+/// ```
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{prompt_secret, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('q'))),
+///     Event::Key(KeyEvent::from(KeyCode::Char('w'))),
+///     Event::Key(KeyEvent::from(KeyCode::Backspace)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('z'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (secret, output) = source.run(|| prompt_secret("Passphrase: "));
+/// assert_eq!(secret.expose_secret(), "qz");
+/// assert_eq!(format!("{:?}", secret), "SecretString([hidden])");
+///
+/// // Nothing typed was drawn
+/// assert!(!output.contains('q') && !output.contains('z'));
+/// assert!(output.contains("[hidden]"));
+/// ```
+pub fn prompt_secret(input_str: &str) -> SecretString {
+    event::with_terminal(|source, out| secret_internal(source, out, input_str, &Theme::default()))
+}
+
+fn secret_internal(source: &mut dyn EventSource, out: &mut Output, input_str: &str, theme: &Theme) -> SecretString {
+    let mut secret = SecretString::new();
+
+    crossterm::queue!(out, crossterm::style::Print(input_str), crossterm::style::PrintStyledContent(theme.hint("[hidden]"))).unwrap();
+
+    loop {
+        match read_event(source, out) {
+            crossterm::event::Event::Key(key) => {
+                if key.kind != crossterm::event::KeyEventKind::Press {
+                    continue;
+                }
+
+                if is_interrupt(&key) {
+                    interrupt(out);
+                }
+
+                match key.code {
+                    crossterm::event::KeyCode::Enter => return secret,
+                    crossterm::event::KeyCode::Backspace => secret.pop_grapheme(),
+                    crossterm::event::KeyCode::Char(c) if !key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) => secret.push(c),
+                    _ => {}
+                }
+            }
+            crossterm::event::Event::Paste(text) => {
+                for c in text.chars().filter(|c| !c.is_control()) {
+                    secret.push(c);
+                }
+
+                // The pasted text is a secret too once it's been added
+                wipe(&mut text.into_bytes());
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::io::IsTerminal;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crate::Theme;
+
+/// Frames of the spinner, one is drawn every `FRAME_TIME`
+const FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+const FRAME_TIME: Duration = Duration::from_millis(80);
+
+/// Run `f` on another thread, animating a spinner at the cursor until it returns, then clear the spinner and return what `f` returned.
+/// Meant for slow work inside a validation closure, like checking a name is free on a server, so the prompt doesn't look frozen.
+/// The spinner only shows up if `f` takes longer than a frame, and isn't drawn at all if stdout isn't a terminal.
+/// A panic in `f` is passed on once the spinner is cleared.
+/// ## Example
+/// ```no_run
+/// use painless_input::{input_with_validation, with_spinner};
+///
+/// # fn is_taken(_: &str) -> bool { false }
+/// let username: String = input_with_validation("Username: ", Box::new(|name: &String| {
+///     if with_spinner(|| is_taken(name)) {
+///         Err(String::from("That name is taken"))
+///     } else {
+///         Ok(())
+///     }
+/// }));
+/// println!();
+/// ```
+/// ```
+/// use painless_input::with_spinner;
+///
+/// let sum = with_spinner(|| (1..=100).sum::<i32>());
+/// assert_eq!(sum, 5050);
+/// ```
+pub fn with_spinner<R, F>(f: F) -> R
+    where
+        F: FnOnce() -> R + Send,
+        R: Send,
+{
+    std::thread::scope(|scope| {
+        let (done_tx, done_rx) = mpsc::channel();
+
+        let worker = scope.spawn(move || {
+            let res = f();
+
+            // The receiver is only gone if the spinner thread panicked, which isn't something to add to
+            let _ = done_tx.send(());
+
+            res
+        });
+
+        // A prompt holds stdout for as long as it's running, but the lock can be taken again on the same thread, so the spinner is drawn from here
+        let mut stdout = std::io::stdout();
+        let draw = stdout.is_terminal();
+        let theme = Theme::default();
+
+        let mut drawn = false;
+
+        for frame in FRAMES.iter().cycle() {
+            match done_rx.recv_timeout(FRAME_TIME) {
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                // Finished, or panicked and dropped the sender
+                _ => break,
+            }
+
+            if draw {
+                // Drawn a column after the cursor, and moved back so whatever is typed next goes where it was
+                let _ = crossterm::execute!(
+                    stdout,
+                    crossterm::style::Print(" "),
+                    crossterm::style::PrintStyledContent(theme.hint(frame)),
+                    crossterm::cursor::MoveLeft(2)
+                );
+
+                drawn = true;
+            }
+        }
+
+        if drawn {
+            let _ = crossterm::execute!(stdout, crossterm::style::Print("  "), crossterm::cursor::MoveLeft(2));
+        }
+
+        match worker.join() {
+            Ok(res) => res,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    })
+}
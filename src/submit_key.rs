@@ -0,0 +1,25 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// The key that submits a prompt.
+/// Prompts that take more than one line use Enter for new lines, so they need one of the other keys to submit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SubmitKey {
+    /// Enter, with or without modifiers
+    #[default]
+    Enter,
+    /// Ctrl+D
+    CtrlD,
+    /// Alt+Enter, which some terminals send for Shift+Enter too
+    AltEnter,
+}
+
+impl SubmitKey {
+    /// Whether `key` is this submit key
+    pub(crate) fn matches(&self, key: &KeyEvent) -> bool {
+        match self {
+            SubmitKey::Enter => key.code == KeyCode::Enter,
+            SubmitKey::CtrlD => key.code == KeyCode::Char('d') && key.modifiers.contains(KeyModifiers::CONTROL),
+            SubmitKey::AltEnter => key.code == KeyCode::Enter && key.modifiers.contains(KeyModifiers::ALT),
+        }
+    }
+}
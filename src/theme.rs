@@ -0,0 +1,295 @@
+use std::fmt::Display;
+
+use crossterm::style::{Attribute, Color, ContentStyle, StyledContent, Stylize};
+
+use crate::display_width;
+
+/// The colors and markers used to draw the prompts.
+/// `Theme::default()` is the look used by the functions without a theme parameter, pass a theme to the `_with_theme` variants to change it.
+///
+/// If the `NO_COLOR` environment variable is set, the styles of any theme are ignored and everything is drawn as plain text.
+/// The markers are still drawn as they carry meaning.
+/// ## Example
+/// ```no_run
+/// use crossterm::style::{ContentStyle, Stylize};
+/// use painless_input::{input_with_theme, Theme};
+///
+/// let theme = Theme {
+///     error_style: ContentStyle::new().yellow().bold(),
+///     ..Theme::default()
+/// };
+///
+/// let num: i32 = input_with_theme("Enter a number: ", &theme);
+/// println!();
+/// ```
+/// ## Bell
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_with_theme, Theme, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('x'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('1'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let theme = Theme { bell: true, ..Theme::default() };
+/// let (_, output) = source.run(|| input_with_theme::<i32>("Enter a number: ", &theme));
+///
+/// // The bell rings once, for the rejected input
+/// assert_eq!(output.matches('\x07').count(), 1);
+/// ```
+/// ## Errors below the input
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input_with_theme, Theme, VecEventSource};
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('x'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('1'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let theme = Theme { error_below: true, ..Theme::plain() };
+/// let (num, output) = source.with_width(24).run(|| input_with_theme::<i32>("Enter a number: ", &theme));
+/// assert_eq!(num, 1);
+///
+/// // Drawn on the next row and cut off to fit, then the cursor goes back up to the end of the input
+/// assert!(output.contains("\r\nInvalid input: 'x'; tr…\x1b[1A\x1b[17G"));
+/// // Typing clears the row below again
+/// assert!(output.contains("\x1b[1B\x1b[2K\x1b[1A1"));
+/// ```
+/// ## Help footer
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{multiselect_input_with_theme, select_input_with_theme, Theme, VecEventSource};
+///
+/// let theme = Theme { help_footer: true, ..Theme::default() };
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Down)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (index, output) = source.run(|| select_input_with_theme("Size: ", &["Small", "Large"], &theme));
+/// assert_eq!(index, 1);
+/// assert!(output.contains("↑/↓ choose · enter confirm"));
+/// // The footer is cleared once the choice is made
+/// assert!(output.ends_with("\r\n\x1b[2K\x1b[1A\x1b[15G\x1b[?25h\x1b[0m\x1b[?25h"));
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char(' '))),
+///     Event::Key(KeyEvent::from(KeyCode::End)),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (selections, output) = source.run(|| multiselect_input_with_theme("Toppings:", "Done", &["Ham", "Olives"], &theme));
+/// assert_eq!(selections, vec![true, false]);
+/// assert!(output.contains("↑/↓ navigate · space toggle"));
+/// // The cursor is left on the row of the submit button, with the footer below it cleared
+/// assert!(output.contains("\x1b[4B\r\x1b[2K\x1b[1A"));
+/// ```
+/// ## NO_COLOR
+/// ```
+/// use crossterm::event::{Event, KeyCode, KeyEvent};
+/// use painless_input::{input, VecEventSource};
+///
+/// std::env::set_var("NO_COLOR", "1");
+///
+/// let source = VecEventSource::new(vec![
+///     Event::Key(KeyEvent::from(KeyCode::Char('x'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+///     Event::Key(KeyEvent::from(KeyCode::Char('1'))),
+///     Event::Key(KeyEvent::from(KeyCode::Enter)),
+/// ]);
+///
+/// let (_, output) = source.run(|| input::<i32>("Enter a number: "));
+///
+/// // Styling escape codes are the ones ending in m, like \x1b[1m
+/// let is_styling = |sequence: &str| {
+///     sequence.strip_prefix('[').and_then(|rest| rest.chars().find(|c| c.is_ascii_alphabetic())) == Some('m')
+/// };
+///
+/// // The error message is shown, but without any styling
+/// assert!(output.contains("Invalid input: 'x'; try again"));
+/// assert!(!output.split('\x1b').any(is_styling));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Theme {
+    /// Style of prompts shown above a list, like the title of a multiselect
+    pub prompt_style: ContentStyle,
+    /// Style of error messages
+    pub error_style: ContentStyle,
+    /// Style of warnings, shown for values that are accepted but look unusual
+    pub warning_style: ContentStyle,
+    /// Style of the current choice in `select_input` and of the submit button
+    pub value_style: ContentStyle,
+    /// Style of the row the cursor is on in the list widgets
+    pub highlight_style: ContentStyle,
+    /// Style of less important text like "(no matches)"
+    pub hint_style: ContentStyle,
+    /// Style of the characters of each option that matched what was typed into `select_filter`, on top of the row's own style
+    pub match_style: ContentStyle,
+    /// Shown in front of the row the cursor is on, useful when the highlight style can't be seen
+    pub highlight_marker: String,
+    /// Checkbox of a selected multiselect option
+    pub selected_marker: String,
+    /// Checkbox of an unselected multiselect option, should be the same width as `selected_marker`
+    pub unselected_marker: String,
+    /// Shown in front of the submit button of a multiselect
+    pub submit_tick: String,
+    /// Shown after the current choice in `select_input`
+    pub up_down_arrow: String,
+    /// Shown next to the top row of a scrolling list when there are more options above
+    pub more_above: String,
+    /// Shown next to the bottom row of a scrolling list when there are more options below
+    pub more_below: String,
+    /// Shown before the elements of an array input
+    pub array_open: String,
+    /// Shown between the elements of an array input
+    pub array_separator: String,
+    /// Shown after the elements of an array input once it's finished
+    pub array_close: String,
+    /// Whether to ring the terminal bell when an input is rejected, as well as showing the error message
+    pub bell: bool,
+    /// Whether the error messages of text inputs are shown on the row below the input instead of after it.
+    /// The message is cut off at the edge of the terminal instead of wrapping, which keeps narrow terminals tidy.
+    pub error_below: bool,
+    /// Whether the select and multiselect widgets show a dimmed line of the keys they use below them, like `↑/↓ navigate · enter confirm`.
+    /// It's off by default, as it's only noise to someone who already knows the keys, and cleared once the prompt is finished.
+    pub help_footer: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            prompt_style: ContentStyle::new().bold(),
+            error_style: ContentStyle::new().with(Color::DarkRed).on(Color::DarkRed).underlined(),
+            warning_style: ContentStyle::new().with(Color::DarkYellow),
+            value_style: ContentStyle::new().bold(),
+            highlight_style: ContentStyle::new().underlined(),
+            hint_style: ContentStyle::new().attribute(Attribute::Dim),
+            match_style: ContentStyle::new().bold(),
+            highlight_marker: String::new(),
+            selected_marker: String::from("☑"),
+            unselected_marker: String::from("☐"),
+            submit_tick: String::from("✓"),
+            up_down_arrow: String::from("⭥"),
+            more_above: String::from("▲"),
+            more_below: String::from("▼"),
+            array_open: String::from("["),
+            array_separator: String::from(", "),
+            array_close: String::from("]"),
+            bell: false,
+            error_below: false,
+            help_footer: false,
+        }
+    }
+}
+
+impl Theme {
+    /// A theme without any colors or text styling, for dumb terminals.
+    /// The row the cursor is on is marked with a `>` instead of being underlined.
+    pub fn plain() -> Self {
+        Self {
+            prompt_style: ContentStyle::new(),
+            error_style: ContentStyle::new(),
+            warning_style: ContentStyle::new(),
+            value_style: ContentStyle::new(),
+            highlight_style: ContentStyle::new(),
+            hint_style: ContentStyle::new(),
+            match_style: ContentStyle::new(),
+            highlight_marker: String::from("> "),
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn prompt<D: Display>(&self, text: D) -> StyledContent<D> {
+        paint(&self.prompt_style, text)
+    }
+
+    pub(crate) fn error<D: Display>(&self, text: D) -> StyledContent<D> {
+        paint(&self.error_style, text)
+    }
+
+    pub(crate) fn warning<D: Display>(&self, text: D) -> StyledContent<D> {
+        paint(&self.warning_style, text)
+    }
+
+    pub(crate) fn value<D: Display>(&self, text: D) -> StyledContent<D> {
+        paint(&self.value_style, text)
+    }
+
+    pub(crate) fn hint<D: Display>(&self, text: D) -> StyledContent<D> {
+        paint(&self.hint_style, text)
+    }
+
+    /// The help footer listing `keys`, cut short to fit in `width` columns, or `None` if the footer is turned off
+    pub(crate) fn footer(&self, keys: &str, width: u16) -> Option<String> {
+        self.help_footer.then(|| self.hint(crate::truncate_to_width(keys, (width as usize).saturating_sub(1))).to_string())
+    }
+
+    /// A row of a list, with the highlight style and marker if the cursor is on it.
+    /// Rows the cursor isn't on are padded so they line up with the highlighted one.
+    pub(crate) fn row(&self, text: &str, highlighted: bool) -> String {
+        if highlighted {
+            format!("{}{}", self.highlight_marker, paint(&self.highlight_style, text))
+        } else {
+            format!("{}{}", " ".repeat(display_width(&self.highlight_marker) as usize), text)
+        }
+    }
+
+    /// Same as `row`, but the chars at `matched`, which are char indices into `text`, are drawn in `match_style` as well
+    pub(crate) fn row_with_matches(&self, text: &str, highlighted: bool, matched: &[usize]) -> String {
+        let (mut row, base_style) = if highlighted {
+            (self.highlight_marker.clone(), self.highlight_style)
+        } else {
+            (" ".repeat(display_width(&self.highlight_marker) as usize), ContentStyle::new())
+        };
+
+        let match_style = ContentStyle {
+            foreground_color: self.match_style.foreground_color.or(base_style.foreground_color),
+            background_color: self.match_style.background_color.or(base_style.background_color),
+            underline_color: self.match_style.underline_color.or(base_style.underline_color),
+            attributes: base_style.attributes | self.match_style.attributes,
+        };
+
+        // Runs of chars that all matched or all didn't are painted together
+        let mut run = String::new();
+        let mut run_matched = false;
+
+        for (i, c) in text.chars().enumerate() {
+            let is_matched = matched.contains(&i);
+
+            if is_matched != run_matched && !run.is_empty() {
+                row.push_str(&paint(if run_matched { &match_style } else { &base_style }, &run).to_string());
+                run.clear();
+            }
+
+            run_matched = is_matched;
+            run.push(c);
+        }
+
+        if !run.is_empty() {
+            row.push_str(&paint(if run_matched { &match_style } else { &base_style }, &run).to_string());
+        }
+
+        row
+    }
+}
+
+/// `text` in `style`, or unstyled if colors are turned off with `NO_COLOR`
+fn paint<D: Display>(style: &ContentStyle, text: D) -> StyledContent<D> {
+    if no_color() {
+        ContentStyle::new().apply(text)
+    } else {
+        style.apply(text)
+    }
+}
+
+/// Whether the `NO_COLOR` environment variable is set to something, see <https://no-color.org>
+pub(crate) fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
@@ -0,0 +1,153 @@
+use std::fmt::Display;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::ValidationFn;
+
+/// The result of a validation that can accept a value with a warning, as well as accepting or rejecting it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Validation {
+    /// The value is accepted
+    Ok,
+    /// The value is accepted, but the message is shown as a note next to it
+    Warn(String),
+    /// The value is rejected, the message is shown and the user has to try again
+    Err(String),
+}
+
+/// A list of rules a value has to pass, built up out of combinators instead of writing the whole check as one closure.
+/// The rules are checked in the order they're added, and the message of the first one that fails is shown.
+/// Call `all_errors` to show the messages of every failing rule instead.
+/// ## Example
+/// ```no_run
+/// use painless_input::{input_with_validator, Validator};
+///
+/// let age: u8 = input_with_validator("Enter your age: ", Validator::new().min(1).max(120));
+/// println!();
+/// ```
+/// ```
+/// use painless_input::Validator;
+///
+/// let validator = Validator::new()
+///     .min(10)
+///     .custom(|x: &i32| if x % 2 == 0 { Ok(()) } else { Err(String::from("Should be even")) });
+///
+/// assert_eq!(validator.validate(&12), Ok(()));
+/// assert_eq!(validator.validate(&3), Err(String::from("Should be at least 10")));
+///
+/// let validator = validator.all_errors();
+/// assert_eq!(validator.validate(&3), Err(String::from("Should be at least 10; Should be even")));
+/// ```
+pub struct Validator<T> {
+    rules: Vec<ValidationFn<T>>,
+    all_errors: bool,
+}
+
+impl<T> Validator<T> {
+    pub fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            all_errors: false,
+        }
+    }
+
+    /// The value has to be at least `min`
+    pub fn min(self, min: T) -> Self
+        where T: PartialOrd + Display + 'static
+    {
+        self.custom(move |value| {
+            if *value >= min {
+                Ok(())
+            } else {
+                Err(format!("Should be at least {}", min))
+            }
+        })
+    }
+
+    /// The value has to be at most `max`
+    pub fn max(self, max: T) -> Self
+        where T: PartialOrd + Display + 'static
+    {
+        self.custom(move |value| {
+            if *value <= max {
+                Ok(())
+            } else {
+                Err(format!("Should be at most {}", max))
+            }
+        })
+    }
+
+    /// The text has to be at least `min_len` characters long, counted as graphemes
+    pub fn min_len(self, min_len: usize) -> Self
+        where T: AsRef<str>
+    {
+        self.custom(move |value| {
+            if value.as_ref().graphemes(true).count() >= min_len {
+                Ok(())
+            } else {
+                Err(format!("Should be at least {} characters", min_len))
+            }
+        })
+    }
+
+    /// The text can be at most `max_len` characters long, counted as graphemes
+    pub fn max_len(self, max_len: usize) -> Self
+        where T: AsRef<str>
+    {
+        self.custom(move |value| {
+            if value.as_ref().graphemes(true).count() <= max_len {
+                Ok(())
+            } else {
+                Err(format!("Should be at most {} characters", max_len))
+            }
+        })
+    }
+
+    /// A rule of its own, returning the error message to be shown if the value is invalid
+    pub fn custom(mut self, rule: impl Fn(&T) -> Result<(), String> + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Check every rule and show all of their messages separated by `; `, instead of only the first one
+    pub fn all_errors(mut self) -> Self {
+        self.all_errors = true;
+        self
+    }
+
+    /// Check `value` against the rules, returning the message to be shown if any of them fail
+    pub fn validate(&self, value: &T) -> Result<(), String> {
+        let mut errors = Vec::new();
+
+        for rule in &self.rules {
+            if let Err(error_msg) = rule(value) {
+                if !self.all_errors {
+                    return Err(error_msg);
+                }
+
+                errors.push(error_msg);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+impl<T> Default for Validator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<ValidationFn<T>> for Validator<T> {
+    fn from(validation: ValidationFn<T>) -> Self {
+        Self {
+            rules: vec![validation],
+            all_errors: false,
+        }
+    }
+}
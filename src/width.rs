@@ -0,0 +1,89 @@
+use unicode_width::UnicodeWidthStr;
+
+/// How many columns `text` takes up in the terminal.
+/// Wide characters like CJK count as 2 and combining characters as 0, and ANSI escape sequences are skipped over as they aren't drawn,
+/// so a prompt styled by hand is measured the same as the plain text.
+/// ## Example
+/// ```
+/// use painless_input::display_width;
+///
+/// assert_eq!(display_width("Name: "), 6);
+/// assert_eq!(display_width("日本"), 4);
+/// assert_eq!(display_width("\x1b[1;31mName:\x1b[0m "), 6);
+/// ```
+pub fn display_width(text: &str) -> u16 {
+    let mut width = 0;
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let len = match escape_len(rest) {
+            Some(len) => len,
+            None => {
+                // Up to the next escape is plain text
+                let len = rest.find('\x1b').unwrap_or(rest.len());
+                width += rest[..len].width();
+                len
+            }
+        };
+
+        rest = &rest[len..];
+    }
+
+    width.min(u16::MAX as usize) as u16
+}
+
+/// The length in bytes of the ANSI escape sequence `text` starts with, if it starts with one.
+/// A lone ESC that doesn't start a sequence counts as one, so it's never measured as text.
+pub(crate) fn escape_len(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+
+    if bytes.first() != Some(&0x1b) {
+        return None;
+    }
+
+    let len = match bytes.get(1) {
+        // CSI, like the styling ones, ends with a byte from @ to ~
+        Some(b'[') => bytes[2..].iter().position(|byte| (0x40..=0x7e).contains(byte)).map_or(bytes.len(), |end| end + 3),
+        // OSC, like hyperlinks, ends with BEL or ESC \
+        Some(b']') => {
+            let body = &bytes[2..];
+
+            (0..body.len())
+                .find_map(|i| match body[i] {
+                    0x07 => Some(i + 3),
+                    0x1b if body.get(i + 1) == Some(&b'\\') => Some(i + 4),
+                    _ => None,
+                })
+                .unwrap_or(bytes.len())
+        }
+        // Any other escape is ESC and one more char
+        Some(_) => 1 + text[1..].chars().next().unwrap().len_utf8(),
+        None => 1,
+    };
+
+    Some(len)
+}
+
+/// Whether `text` has any right-to-left characters, like Hebrew or Arabic, which some terminals reorder when drawing them.
+/// The cursor is still placed by counting columns in the order the text was typed, but a line with these is redrawn whole on each edit
+/// so nothing is left garbled where the terminal put it.
+pub(crate) fn contains_rtl(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(
+            c,
+            // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, Mandaic and the Arabic extensions
+            '\u{0590}'..='\u{08FF}'
+            // Hebrew and Arabic presentation forms
+            | '\u{FB1D}'..='\u{FDFF}'
+            | '\u{FE70}'..='\u{FEFF}'
+            // Right-to-left scripts outside the basic plane, like Phoenician and Adlam
+            | '\u{10800}'..='\u{10FFF}'
+            | '\u{1E800}'..='\u{1EFFF}'
+            // Right-to-left mark and embedding, override and isolate controls
+            | '\u{200F}'
+            | '\u{202B}'
+            | '\u{202E}'
+            | '\u{2067}'
+        )
+    })
+}